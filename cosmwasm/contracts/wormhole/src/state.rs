@@ -28,11 +28,6 @@ use crate::{
     error::ContractError,
 };
 
-use sha3::{
-    Digest,
-    Keccak256,
-};
-
 type HumanAddr = String;
 
 pub static CONFIG_KEY: &[u8] = b"config";
@@ -153,54 +148,31 @@ impl ParsedVAA {
     // Recovery byte is last after the main signature
     pub const SIG_RECOVERY_POS: usize = Self::SIG_DATA_POS + Self::SIG_DATA_LEN;
 
+    /// Parse a VAA with `wormhole_core`, so that every chain agrees exactly on which VAAs are
+    /// valid.
     pub fn deserialize(data: &[u8]) -> StdResult<Self> {
-        let version = data.get_u8(0);
-
-        // Load 4 bytes starting from index 1
-        let guardian_set_index: u32 = data.get_u32(Self::GUARDIAN_SET_INDEX_POS);
-        let len_signers = data.get_u8(Self::LEN_SIGNER_POS) as usize;
-        let body_offset: usize = Self::HEADER_LEN + Self::SIGNATURE_LEN * len_signers as usize;
-
-        // Hash the body
-        if body_offset >= data.len() {
-            return ContractError::InvalidVAA.std_err();
-        }
-        let body = &data[body_offset..];
-        let mut hasher = Keccak256::new();
-        hasher.update(body);
-        let hash = hasher.finalize().to_vec();
-
-        // Rehash the hash
-        let mut hasher = Keccak256::new();
-        hasher.update(hash);
-        let hash = hasher.finalize().to_vec();
-
-        // Signatures valid, apply VAA
-        if body_offset + Self::VAA_PAYLOAD_POS > data.len() {
-            return ContractError::InvalidVAA.std_err();
-        }
+        let vaa = match wormhole_core::VAA::from_bytes(data) {
+            Ok(vaa) => vaa,
+            Err(_) => return ContractError::InvalidVAA.std_err(),
+        };
 
-        let timestamp = data.get_u32(body_offset);
-        let nonce = data.get_u32(body_offset + Self::VAA_NONCE_POS);
-        let emitter_chain = data.get_u16(body_offset + Self::VAA_EMITTER_CHAIN_POS);
-        let emitter_address = data
-            .get_bytes32(body_offset + Self::VAA_EMITTER_ADDRESS_POS)
-            .to_vec();
-        let sequence = data.get_u64(body_offset + Self::VAA_SEQUENCE_POS);
-        let consistency_level = data.get_u8(body_offset + Self::VAA_CONSISTENCY_LEVEL_POS);
-        let payload = data[body_offset + Self::VAA_PAYLOAD_POS..].to_vec();
+        // Guardians sign the hash of the body hash.
+        let hash = match vaa.digest() {
+            Some(digest) => digest.secp256k_hash.to_vec(),
+            None => return ContractError::InvalidVAA.std_err(),
+        };
 
         Ok(ParsedVAA {
-            version,
-            guardian_set_index,
-            timestamp,
-            nonce,
-            len_signers: len_signers as u8,
-            emitter_chain,
-            emitter_address,
-            sequence,
-            consistency_level,
-            payload,
+            version: vaa.version,
+            guardian_set_index: vaa.guardian_set_index,
+            timestamp: vaa.timestamp,
+            nonce: vaa.nonce,
+            len_signers: vaa.signatures.len() as u8,
+            emitter_chain: vaa.emitter_chain.into(),
+            emitter_address: vaa.emitter_address.to_vec(),
+            sequence: vaa.sequence,
+            consistency_level: vaa.consistency_level,
+            payload: vaa.payload,
             hash,
         })
     }
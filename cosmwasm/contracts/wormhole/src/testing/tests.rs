@@ -1,5 +1,7 @@
-use cosmwasm_std::StdResult;
+use cosmwasm_std::{StdError, StdResult};
+use sha3::{Digest, Keccak256};
 
+use crate::byte_utils::ByteUtils;
 use crate::state::{GuardianAddress, GuardianSetInfo, ParsedVAA};
 
 #[test]
@@ -159,4 +161,68 @@ fn deserialize_round_2() -> StdResult<()> {
     assert_eq!(parsed.hash, hash, "parsed.hash != expected");
 
     Ok(())
-}
\ No newline at end of file
+}
+
+// The parser `ParsedVAA::deserialize` used before delegating to `wormhole_core`, kept to check
+// parity. It panics rather than erroring on some truncated inputs.
+fn legacy_deserialize(data: &[u8]) -> StdResult<ParsedVAA> {
+    let version = data.get_u8(0);
+    let guardian_set_index: u32 = data.get_u32(ParsedVAA::GUARDIAN_SET_INDEX_POS);
+    let len_signers = data.get_u8(ParsedVAA::LEN_SIGNER_POS) as usize;
+    let body_offset: usize = ParsedVAA::HEADER_LEN + ParsedVAA::SIGNATURE_LEN * len_signers;
+
+    if body_offset >= data.len() {
+        return Err(StdError::generic_err("InvalidVAA"));
+    }
+    let body = &data[body_offset..];
+    let hash = Keccak256::digest(Keccak256::digest(body).as_slice()).to_vec();
+
+    if body_offset + ParsedVAA::VAA_PAYLOAD_POS > data.len() {
+        return Err(StdError::generic_err("InvalidVAA"));
+    }
+
+    Ok(ParsedVAA {
+        version,
+        guardian_set_index,
+        timestamp: data.get_u32(body_offset),
+        nonce: data.get_u32(body_offset + ParsedVAA::VAA_NONCE_POS),
+        len_signers: len_signers as u8,
+        emitter_chain: data.get_u16(body_offset + ParsedVAA::VAA_EMITTER_CHAIN_POS),
+        emitter_address: data
+            .get_bytes32(body_offset + ParsedVAA::VAA_EMITTER_ADDRESS_POS)
+            .to_vec(),
+        sequence: data.get_u64(body_offset + ParsedVAA::VAA_SEQUENCE_POS),
+        consistency_level: data.get_u8(body_offset + ParsedVAA::VAA_CONSISTENCY_LEVEL_POS),
+        payload: data[body_offset + ParsedVAA::VAA_PAYLOAD_POS..].to_vec(),
+        hash,
+    })
+}
+
+#[test]
+fn deserialize_parity() {
+    let signed_vaa = "\
+        010000000001003f3179d5bb17b6f2ecc13741ca3f78d922043e99e09975e390\
+        4332d2418bb3f16d7ac93ca8401f8bed1cf9827bc806ecf7c5a283340f033bf4\
+        72724abf1d274f00000000000000000000010000000000000000000000000000\
+        00000000000000000000000000000000ffff0000000000000000000100000000\
+        00000000000000000000000000000000000000000000000005f5e10001000000\
+        0000000000000000000000000000000000000000000000007575736400030000\
+        00000000000000000000f7f7dde848e7450a029cd0a9bd9bdae4b5147db30003\
+        00000000000000000000000000000000000000000000000000000000000f4240";
+    let signed_vaa = hex::decode(signed_vaa).unwrap();
+
+    // Every truncation must be accepted or rejected by both parsers alike, where a panic in the
+    // legacy parser counts as a rejection. The new parser must never panic.
+    for len in 0..=signed_vaa.len() {
+        let data = &signed_vaa[..len];
+        let new = ParsedVAA::deserialize(data);
+        let old = std::panic::catch_unwind(|| legacy_deserialize(data))
+            .unwrap_or_else(|_| Err(StdError::generic_err("panic")));
+
+        match (new, old) {
+            (Ok(new), Ok(old)) => assert_eq!(new, old, "parsers disagree at length {}", len),
+            (Err(_), Err(_)) => {}
+            _ => panic!("parsers disagree at length {}", len),
+        }
+    }
+}
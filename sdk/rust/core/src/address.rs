@@ -0,0 +1,278 @@
+//! Wormhole identifies accounts on every chain with a 32-byte address. Chains with shorter native
+//! addresses left-pad them with zeroes, and chains whose native identifiers do not fit, such as
+//! Cosmos bank denoms, hash them instead. `UniversalAddress` collects these encodings in one place
+//! so that every contract and client derives the same bytes for the same account.
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+#[cfg(feature = "alloc")]
+use bech32::{
+    FromBase32,
+    ToBase32,
+    Variant,
+};
+use sha3::Digest;
+
+use crate::{
+    ForeignAddress,
+    WormholeError,
+};
+
+/// Marker written to the first byte of the external ID of a CW20 token native to a Cosmos chain.
+pub const CW20_MARKER: u8 = 0;
+
+/// Marker written to the first byte of the external ID of a bank token native to a Cosmos chain.
+pub const BANK_MARKER: u8 = 1;
+
+/// A 32-byte address as it appears in Wormhole messages.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", transparent)
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+pub struct UniversalAddress(
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] pub ForeignAddress,
+);
+
+impl UniversalAddress {
+    pub const fn new(bytes: ForeignAddress) -> Self {
+        UniversalAddress(bytes)
+    }
+
+    pub const fn to_bytes(self) -> ForeignAddress {
+        self.0
+    }
+
+    /// Left-pad a native address of at most 32 bytes.
+    pub fn from_native(bytes: &[u8]) -> Result<Self, WormholeError> {
+        let offset = 32usize
+            .checked_sub(bytes.len())
+            .ok_or(WormholeError::InvalidAddress)?;
+        let mut address = [0u8; 32];
+        address[offset..].copy_from_slice(bytes);
+        Ok(UniversalAddress(address))
+    }
+
+    /// Strip the padding from a native address of `len` bytes, failing if any padding byte is
+    /// non-zero, as that means the address belongs to some other chain.
+    pub fn to_native(&self, len: usize) -> Result<&[u8], WormholeError> {
+        let offset = 32usize
+            .checked_sub(len)
+            .ok_or(WormholeError::InvalidAddress)?;
+        let (padding, native) = self.0.split_at(offset);
+        match padding.iter().all(|&b| b == 0) {
+            true => Ok(native),
+            false => Err(WormholeError::InvalidAddress),
+        }
+    }
+
+    /// Parse a base58 Solana address.
+    #[cfg(feature = "alloc")]
+    pub fn from_solana(address: &str) -> Result<Self, WormholeError> {
+        let bytes = bs58::decode(address)
+            .into_vec()
+            .map_err(|_| WormholeError::InvalidAddress)?;
+        match bytes.len() {
+            32 => Self::from_native(&bytes),
+            _ => Err(WormholeError::InvalidAddress),
+        }
+    }
+
+    /// Encode as a base58 Solana address, every 32-byte value is a valid Solana address.
+    #[cfg(feature = "alloc")]
+    pub fn to_solana(&self) -> String {
+        bs58::encode(&self.0).into_string()
+    }
+
+    /// Parse a 20-byte EVM address in hex, with or without a `0x` prefix. Checksums are not
+    /// verified.
+    pub fn from_evm(address: &str) -> Result<Self, WormholeError> {
+        let address = address.strip_prefix("0x").unwrap_or(address);
+        let mut bytes = [0u8; 20];
+        hex::decode_to_slice(address, &mut bytes).map_err(|_| WormholeError::InvalidAddress)?;
+        Self::from_native(&bytes)
+    }
+
+    /// The 20-byte EVM address, failing if the 12 padding bytes are not zero.
+    pub fn to_evm(&self) -> Result<[u8; 20], WormholeError> {
+        let mut address = [0u8; 20];
+        address.copy_from_slice(self.to_native(20)?);
+        Ok(address)
+    }
+
+    /// The EVM address as lowercase hex with a `0x` prefix.
+    #[cfg(feature = "alloc")]
+    pub fn to_evm_hex(&self) -> Result<String, WormholeError> {
+        Ok(["0x", &hex::encode(self.to_evm()?)].concat())
+    }
+
+    /// Parse a bech32 Cosmos address with any prefix. Account addresses are 20 bytes and are
+    /// left-padded, matching the canonical address padding used by the Terra contracts, while
+    /// 32-byte contract addresses are used as is.
+    #[cfg(feature = "alloc")]
+    pub fn from_bech32(address: &str) -> Result<Self, WormholeError> {
+        let (_, data, variant) =
+            bech32::decode(address).map_err(|_| WormholeError::InvalidAddress)?;
+        let bytes = Vec::<u8>::from_base32(&data).map_err(|_| WormholeError::InvalidAddress)?;
+        match (variant, bytes.len()) {
+            (Variant::Bech32, 20) | (Variant::Bech32, 32) => Self::from_native(&bytes),
+            _ => Err(WormholeError::InvalidAddress),
+        }
+    }
+
+    /// Encode as a bech32 Cosmos address with the prefix `hrp`, such as `terra`. Addresses with
+    /// zero padding are encoded as 20-byte account addresses, anything else as a 32-byte address.
+    #[cfg(feature = "alloc")]
+    pub fn to_bech32(&self, hrp: &str) -> Result<String, WormholeError> {
+        let bytes = self.to_native(20).unwrap_or(&self.0);
+        bech32::encode(hrp, bytes.to_base32(), Variant::Bech32)
+            .map_err(|_| WormholeError::InvalidAddress)
+    }
+
+    /// The external ID of a bank token native to a Cosmos chain, identified by its denom.
+    pub fn from_bank_denom(denom: &str) -> Self {
+        Self::external_id(BANK_MARKER, denom)
+    }
+
+    /// The external ID of a CW20 token native to a Cosmos chain, identified by its bech32 contract
+    /// address.
+    pub fn from_cw20(contract: &str) -> Self {
+        Self::external_id(CW20_MARKER, contract)
+    }
+
+    /// Native Cosmos tokens have identifiers that do not fit in 32 bytes, so their external ID is
+    /// the keccak256 hash of the identifier with the first byte replaced by a marker. The hash
+    /// cannot be reversed, so the token bridge stores the identifier when the token is attested.
+    fn external_id(marker: u8, id: &str) -> Self {
+        let mut hash: ForeignAddress = sha3::Keccak256::digest(id.as_bytes()).into();
+        hash[0] = marker;
+        UniversalAddress(hash)
+    }
+}
+
+impl From<ForeignAddress> for UniversalAddress {
+    fn from(bytes: ForeignAddress) -> Self {
+        UniversalAddress(bytes)
+    }
+}
+
+impl From<UniversalAddress> for ForeignAddress {
+    fn from(address: UniversalAddress) -> Self {
+        address.0
+    }
+}
+
+impl AsRef<[u8]> for UniversalAddress {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Displays as 64 hex characters, the form used by the guardian REST API.
+impl fmt::Display for UniversalAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|b| write!(f, "{:02x}", b))
+    }
+}
+
+/// Parses 64 hex characters, with or without a `0x` prefix.
+impl FromStr for UniversalAddress {
+    type Err = WormholeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_prefix("0x").unwrap_or(s);
+        let mut bytes = [0u8; 32];
+        hex::decode_to_slice(s, &mut bytes).map_err(|_| WormholeError::InvalidAddress)?;
+        Ok(UniversalAddress(bytes))
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn test_solana() {
+        let address = "worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth";
+        let universal = UniversalAddress::from_solana(address).unwrap();
+        assert_eq!(universal.to_solana(), address);
+
+        // Base58 strings that do not decode to exactly 32 bytes are rejected.
+        assert!(UniversalAddress::from_solana("worm").is_err());
+        assert!(UniversalAddress::from_solana("0OIl").is_err());
+    }
+
+    #[test]
+    fn test_evm() {
+        let address = "0x98f3c9e6e3face36baad05fe09d375ef1464288b";
+        let universal = UniversalAddress::from_evm(address).unwrap();
+        assert_eq!(&universal.0[..12], &[0u8; 12]);
+        assert_eq!(universal.to_evm_hex().unwrap(), address);
+        assert_eq!(
+            UniversalAddress::from_evm(&address[2..]).unwrap(),
+            universal
+        );
+
+        // Non-zero padding means the address is not an EVM address.
+        let mut padded = universal;
+        padded.0[0] = 1;
+        assert!(matches!(
+            padded.to_evm(),
+            Err(WormholeError::InvalidAddress)
+        ));
+
+        assert!(UniversalAddress::from_evm("0x98f3").is_err());
+    }
+
+    #[test]
+    fn test_bech32() {
+        // Account addresses are padded from 20 bytes.
+        let address = "terra1dq03ugtd40zu9hcgdzrsq6z2z4hwhc9tqk2uy5";
+        let universal = UniversalAddress::from_bech32(address).unwrap();
+        assert_eq!(&universal.0[..12], &[0u8; 12]);
+        assert_eq!(universal.to_bech32("terra").unwrap(), address);
+
+        // 32-byte contract addresses round trip unpadded.
+        let contract = UniversalAddress::new([0xab; 32]);
+        let encoded = contract.to_bech32("terra").unwrap();
+        assert_eq!(UniversalAddress::from_bech32(&encoded).unwrap(), contract);
+
+        // Bad checksums are rejected.
+        assert!(
+            UniversalAddress::from_bech32("terra1dq03ugtd40zu9hcgdzrsq6z2z4hwhc9tqk2uy6").is_err()
+        );
+    }
+
+    #[test]
+    fn test_external_id() {
+        let bank = UniversalAddress::from_bank_denom("uluna");
+        let cw20 = UniversalAddress::from_cw20("terra1dq03ugtd40zu9hcgdzrsq6z2z4hwhc9tqk2uy5");
+        assert_eq!(bank.0[0], BANK_MARKER);
+        assert_eq!(cw20.0[0], CW20_MARKER);
+
+        // The remaining bytes are the tail of the keccak256 hash of the identifier.
+        let hash = sha3::Keccak256::digest(b"uluna");
+        assert_eq!(&bank.0[1..], &hash[1..]);
+    }
+
+    #[test]
+    fn test_hex() {
+        let universal = UniversalAddress::new([0x01; 32]);
+        let encoded = universal.to_string();
+        assert_eq!(encoded.len(), 64);
+        assert_eq!(encoded.parse::<UniversalAddress>().unwrap(), universal);
+        let prefixed = ["0x", &encoded].concat();
+        assert_eq!(prefixed.parse::<UniversalAddress>().unwrap(), universal);
+        assert!("0x0101".parse::<UniversalAddress>().is_err());
+    }
+}
@@ -1,42 +1,284 @@
 //! Exposes an API implementation depending on which feature flags have been toggled for the
 //! library. Check submodules for chain runtime specific documentation.
-use std::convert::TryFrom; // Remove in 2021
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::hash::{
+    Hash,
+    Hasher,
+};
+use core::str::FromStr;
+
+use crate::WormholeError;
 
 
 /// Chain contains a mapping of Wormhole supported chains to their u16 representation. These are
 /// universally defined among all Wormhole contracts.
-#[repr(u16)]
-#[derive(Clone, Debug, PartialEq)]
+///
+/// Chain IDs without a named variant are preserved as `Unknown` rather than rejected, so that
+/// messages from chains newer than this library can still be parsed. `Chain::from_id` always
+/// produces the named variant when one exists, while `TryFrom<u16>` rejects IDs without one with
+/// `WormholeError::InvalidChain`.
+///
+/// `Unknown` must only be built through `Chain::from_id`. Equality is defined on the numeric ID, so
+/// `Chain::Unknown(1) == Chain::Solana`, but a hand-built `Chain::Unknown(1)` does not match a
+/// `Chain::Solana` pattern.
+///
+/// As `Unknown` carries its ID the enum is no longer `#[repr(u16)]`, so `chain as u16` casts must
+/// be written as `u16::from(chain)`.
+#[derive(Clone, Copy, Debug)]
 pub enum Chain {
-    All      = 0,
-    Solana   = 1,
-    Ethereum = 2,
-    Terra    = 3,
-    Binance  = 4,
-    Polygon  = 5,
-    AVAX     = 6,
-    Oasis    = 7,
+    All,
+    Solana,
+    Ethereum,
+    Terra,
+    Binance,
+    Polygon,
+    AVAX,
+    Oasis,
+    Algorand,
+    Aurora,
+    Fantom,
+    Karura,
+    Acala,
+    Klaytn,
+    Celo,
+    Near,
+    Moonbeam,
+    Neon,
+    Terra2,
+    Injective,
+    PythNet,
+    EthereumRopsten,
+    Unknown(u16),
 }
 
+/// Mapping of named chains to their ID and canonical name, the names match those used by the
+/// guardian node.
+const CHAINS: &[(Chain, u16, &str)] = &[
+    (Chain::All, 0, "all"),
+    (Chain::Solana, 1, "solana"),
+    (Chain::Ethereum, 2, "ethereum"),
+    (Chain::Terra, 3, "terra"),
+    (Chain::Binance, 4, "bsc"),
+    (Chain::Polygon, 5, "polygon"),
+    (Chain::AVAX, 6, "avalanche"),
+    (Chain::Oasis, 7, "oasis"),
+    (Chain::Algorand, 8, "algorand"),
+    (Chain::Aurora, 9, "aurora"),
+    (Chain::Fantom, 10, "fantom"),
+    (Chain::Karura, 11, "karura"),
+    (Chain::Acala, 12, "acala"),
+    (Chain::Klaytn, 13, "klaytn"),
+    (Chain::Celo, 14, "celo"),
+    (Chain::Near, 15, "near"),
+    (Chain::Moonbeam, 16, "moonbeam"),
+    (Chain::Neon, 17, "neon"),
+    (Chain::Terra2, 18, "terra2"),
+    (Chain::Injective, 19, "injective"),
+    (Chain::PythNet, 26, "pythnet"),
+    (Chain::EthereumRopsten, 10001, "ethereum-ropsten"),
+];
+
+impl Chain {
+    /// The canonical name of a known chain, `None` for `Unknown` IDs.
+    pub fn name(&self) -> Option<&'static str> {
+        let id = u16::from(*self);
+        CHAINS
+            .iter()
+            .find(|(_, i, _)| *i == id)
+            .map(|(_, _, name)| *name)
+    }
+
+    /// Whether this chain has a named variant in this version of the library.
+    pub fn is_known(&self) -> bool {
+        self.name().is_some()
+    }
+
+    /// Convert any chain ID, falling back to `Unknown` for IDs without a named variant.
+    pub fn from_id(id: u16) -> Chain {
+        CHAINS
+            .iter()
+            .find(|(_, i, _)| *i == id)
+            .map_or(Chain::Unknown(id), |(chain, _, _)| *chain)
+    }
+}
+
+/// Only chains with a named variant convert, for callers that must reject chains they do not know.
+/// Use `Chain::from_id` to accept any ID.
 impl TryFrom<u16> for Chain {
-    type Error = ();
+    type Error = WormholeError;
+
     fn try_from(other: u16) -> Result<Chain, Self::Error> {
+        match Chain::from_id(other) {
+            Chain::Unknown(_) => Err(WormholeError::InvalidChain),
+            chain => Ok(chain),
+        }
+    }
+}
+
+impl From<Chain> for u16 {
+    fn from(other: Chain) -> u16 {
         match other {
-            0 => Ok(Chain::All),
-            1 => Ok(Chain::Solana),
-            2 => Ok(Chain::Ethereum),
-            3 => Ok(Chain::Terra),
-            4 => Ok(Chain::Binance),
-            5 => Ok(Chain::Polygon),
-            6 => Ok(Chain::AVAX),
-            7 => Ok(Chain::Oasis),
-            _ => Err(()),
+            Chain::All => 0,
+            Chain::Solana => 1,
+            Chain::Ethereum => 2,
+            Chain::Terra => 3,
+            Chain::Binance => 4,
+            Chain::Polygon => 5,
+            Chain::AVAX => 6,
+            Chain::Oasis => 7,
+            Chain::Algorand => 8,
+            Chain::Aurora => 9,
+            Chain::Fantom => 10,
+            Chain::Karura => 11,
+            Chain::Acala => 12,
+            Chain::Klaytn => 13,
+            Chain::Celo => 14,
+            Chain::Near => 15,
+            Chain::Moonbeam => 16,
+            Chain::Neon => 17,
+            Chain::Terra2 => 18,
+            Chain::Injective => 19,
+            Chain::PythNet => 26,
+            Chain::EthereumRopsten => 10001,
+            Chain::Unknown(id) => id,
         }
     }
 }
 
+impl PartialEq for Chain {
+    fn eq(&self, other: &Self) -> bool {
+        u16::from(*self) == u16::from(*other)
+    }
+}
+
+impl Eq for Chain {
+}
+
+impl Hash for Chain {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        u16::from(*self).hash(state)
+    }
+}
+
+impl PartialOrd for Chain {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Chain {
+    fn cmp(&self, other: &Self) -> Ordering {
+        u16::from(*self).cmp(&u16::from(*other))
+    }
+}
+
+/// Known chains display as their canonical name, unknown chains as their numeric ID. The output is
+/// always accepted by `FromStr`.
+impl fmt::Display for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "{}", u16::from(*self)),
+        }
+    }
+}
+
+/// Parses either a case-insensitive chain name or a numeric chain ID.
+impl FromStr for Chain {
+    type Err = WormholeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(id) = s.parse::<u16>() {
+            return Ok(Chain::from_id(id));
+        }
+
+        CHAINS
+            .iter()
+            .find(|(_, _, name)| name.eq_ignore_ascii_case(s))
+            .map(|(chain, _, _)| *chain)
+            .ok_or(WormholeError::InvalidChain)
+    }
+}
+
 impl Default for Chain {
     fn default() -> Self {
         Self::All
     }
 }
+
+/// Chains are serialized as their numeric ID, which unlike the name is stable for chains unknown
+/// to this library.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Chain {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16(u16::from(*self))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Chain {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <u16 as serde::Deserialize>::deserialize(deserializer).map(Chain::from_id)
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshSerialize for Chain {
+    fn serialize<W: borsh::maybestd::io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> borsh::maybestd::io::Result<()> {
+        borsh::BorshSerialize::serialize(&u16::from(*self), writer)
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshDeserialize for Chain {
+    fn deserialize(buf: &mut &[u8]) -> borsh::maybestd::io::Result<Self> {
+        <u16 as borsh::BorshDeserialize>::deserialize(buf).map(Chain::from_id)
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::{
+        Chain,
+        CHAINS,
+    };
+    use crate::WormholeError;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn test_chain_id_roundtrip() {
+        for id in 0..=u16::MAX {
+            let chain = Chain::from_id(id);
+            assert_eq!(u16::from(chain), id);
+            assert_eq!(chain.to_string().parse::<Chain>().unwrap(), chain);
+        }
+
+        // The lookup table and the explicit match must agree.
+        for (chain, id, _) in CHAINS {
+            assert_eq!(u16::from(*chain), *id);
+            assert!(chain.is_known());
+        }
+    }
+
+    #[test]
+    fn test_chain_names() {
+        assert_eq!(Chain::from_id(15), Chain::Near);
+        assert_eq!(Chain::from_id(20), Chain::Unknown(20));
+        assert!(!Chain::from_id(20).is_known());
+        assert!(matches!(Chain::try_from(15), Ok(Chain::Near)));
+        assert!(matches!(Chain::try_from(20), Err(WormholeError::InvalidChain)));
+        assert_eq!(Chain::Unknown(1), Chain::Solana);
+        assert_eq!(Chain::Binance.to_string(), "bsc");
+        assert_eq!(Chain::Unknown(15).to_string(), "near");
+        assert_eq!(Chain::Unknown(20).to_string(), "20");
+        assert_eq!("Terra2".parse::<Chain>().unwrap(), Chain::Terra2);
+        assert_eq!("10001".parse::<Chain>().unwrap(), Chain::EthereumRopsten);
+        assert!("mainnet".parse::<Chain>().is_err());
+        assert!("65536".parse::<Chain>().is_err());
+    }
+}
@@ -1,3 +1,7 @@
+use core::fmt;
+
+use nom::error::ErrorKind;
+
 /// Ergonomic error handler for use within the Wormhole core/SDK libraries.
 #[macro_export]
 macro_rules! require {
@@ -8,15 +12,170 @@ macro_rules! require {
     }
 }
 
-/// This ErrorCode maps to the nom ParseError, we use an integer because the library is deprecating
-/// the current error type, so we should avoid depending on it for now.
-type ErrorCode = usize;
-
 #[derive(Debug)]
 pub enum WormholeError {
     InvalidGovernanceAction,
     InvalidGovernanceChain,
     InvalidGovernanceModule,
+    InvalidChain,
+    InvalidNetwork,
+    InvalidAddress,
     DeserializeFailed,
-    ParseError(ErrorCode),
+    SerializeFailed,
+    NoQuorum,
+    WrongGuardianIndexOrder,
+    InvalidGuardianIndex,
+    CannotDecodeSignature,
+    CannotRecoverKey,
+    GuardianSignatureError,
+    ParseError(ParseError),
+}
+
+impl fmt::Display for WormholeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use WormholeError::*;
+        match self {
+            InvalidGovernanceAction => f.write_str("governance action does not match"),
+            InvalidGovernanceChain => f.write_str("governance target chain does not match"),
+            InvalidGovernanceModule => f.write_str("governance module does not match"),
+            InvalidChain => f.write_str("unknown chain name"),
+            InvalidNetwork => f.write_str("unknown network name"),
+            InvalidAddress => f.write_str("invalid address"),
+            DeserializeFailed => f.write_str("failed to deserialize account data"),
+            SerializeFailed => f.write_str("failed to serialize"),
+            NoQuorum => f.write_str("not enough signatures to reach quorum"),
+            WrongGuardianIndexOrder => f.write_str("guardian indices are not strictly ascending"),
+            InvalidGuardianIndex => f.write_str("guardian index is outside the guardian set"),
+            CannotDecodeSignature => f.write_str("cannot decode signature"),
+            CannotRecoverKey => f.write_str("cannot recover key from signature"),
+            GuardianSignatureError => f.write_str("signature does not match guardian key"),
+            ParseError(e) => e.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WormholeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WormholeError::ParseError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for WormholeError {
+    fn from(other: ParseError) -> Self {
+        WormholeError::ParseError(other)
+    }
+}
+
+/// Names a field within a Wormhole wire format. Repeated fields, such as signatures, carry the
+/// index of the failing element and display as `signature[3]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Field {
+    pub name:  &'static str,
+    pub index: Option<usize>,
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.index {
+            Some(index) => write!(f, "{}[{}]", self.name, index),
+            None => f.write_str(self.name),
+        }
+    }
+}
+
+/// Why a field failed to parse.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The input ended before the field was complete.
+    Truncated { expected: usize, actual: usize },
+
+    /// The field was complete but holds a value that is not allowed, such as an unexpected
+    /// payload ID or invalid UTF-8.
+    InvalidValue,
+
+    /// Every field parsed, but input remains where the message should have ended.
+    TrailingBytes { count: usize },
+}
+
+/// Describes exactly where parsing failed. The offset is relative to the start of the input passed
+/// to the `from_bytes` call that failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The failing field, `None` only if a parser outside of this library failed without naming
+    /// the field it was parsing.
+    pub field:  Option<Field>,
+    pub offset: usize,
+    pub kind:   ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.field {
+            Some(field) => write!(f, "failed to parse {} at byte {}", field, self.offset)?,
+            None => write!(f, "failed to parse input at byte {}", self.offset)?,
+        }
+
+        match self.kind {
+            ParseErrorKind::Truncated { expected, actual } => {
+                write!(f, ": expected {} bytes, found {}", expected, actual)
+            }
+            ParseErrorKind::InvalidValue => f.write_str(": invalid value"),
+            ParseErrorKind::TrailingBytes { count } => {
+                write!(f, ": {} unexpected trailing bytes", count)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {
+}
+
+/// The error type used by all nom parsers in this library. It tracks the input at the start of the
+/// failing field, which is converted to an offset once the original input is known.
+#[derive(Debug, PartialEq)]
+pub struct NomError<'a> {
+    pub input: &'a [u8],
+    pub field: Option<Field>,
+    pub kind:  ParseErrorKind,
+}
+
+impl<'a> NomError<'a> {
+    /// Convert to a `WormholeError`, computing the offset from the original input the failing
+    /// parser was given.
+    pub fn into_error(self, original: &[u8]) -> WormholeError {
+        WormholeError::ParseError(ParseError {
+            field:  self.field,
+            offset: original.len().saturating_sub(self.input.len()),
+            kind:   self.kind,
+        })
+    }
+}
+
+impl<'a> nom::error::ParseError<&'a [u8]> for NomError<'a> {
+    /// Errors raised by nom itself do not know which field they belong to, nor how long it is, they
+    /// are refined by `vaa::field` as they propagate.
+    fn from_error_kind(input: &'a [u8], kind: ErrorKind) -> Self {
+        let kind = match kind {
+            ErrorKind::Eof => ParseErrorKind::Truncated {
+                expected: input.len() + 1,
+                actual:   input.len(),
+            },
+            _ => ParseErrorKind::InvalidValue,
+        };
+
+        Self {
+            input,
+            field: None,
+            kind,
+        }
+    }
+
+    fn append(_: &'a [u8], _: ErrorKind, other: Self) -> Self {
+        other
+    }
 }
@@ -1,31 +1,49 @@
 #![deny(unused_results)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "borsh")]
+extern crate borsh_crate as borsh;
+#[cfg(feature = "serde")]
+extern crate serde_crate as serde;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+pub use address::*;
 pub use chain::*;
 pub use error::*;
+pub use payload::*;
 pub use vaa::*;
 
 
+pub mod address;
 pub mod chain;
+pub mod payload;
 pub mod vaa;
 
 #[macro_use]
 pub mod error;
 
+#[cfg(feature = "serde")]
+mod serde_hex;
+
 
 /// Helper method that attempts to parse and truncate UTF-8 from a byte stream. This is useful when
 /// the wire data is expected to contain UTF-8 that is either already truncated, or needs to be,
 /// while still maintaining the ability to render.
 ///
 /// This should be used to parse any Text-over-Wormhole fields that are meant to be human readable.
+#[cfg(feature = "alloc")]
 pub(crate) fn parse_fixed_utf8<T: AsRef<[u8]>, const N: usize>(s: T) -> Option<String> {
     use bstr::ByteSlice;
-    use std::io::Cursor;
-    use std::io::Read;
 
     // Read Bytes.
-    let mut cursor = Cursor::new(s.as_ref());
-    let mut buffer = vec![0u8; N];
-    cursor.read_exact(&mut buffer).ok()?;
+    let mut buffer = s.as_ref().get(..N)?.to_vec();
     buffer.retain(|&c| c != 0);
 
     // Attempt UTF-8 Decoding. Stripping invalid Unicode characters (0xFFFD).
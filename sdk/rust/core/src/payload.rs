@@ -0,0 +1,342 @@
+//! Applications that send their own messages over Wormhole usually define several payloads, told
+//! apart by a leading payload ID byte in the same way as the token and NFT bridge payloads. The
+//! `Payload` trait declares the ID of a single payload, and `payloads!` gathers payloads into an
+//! enum that decodes whichever one it is given.
+//!
+//! Payload IDs double as versions. A payload whose layout changes is registered again under a new
+//! ID rather than changed in place, so messages already emitted with the old layout keep decoding.
+//!
+//! ```rust
+//! use wormhole_core::{
+//!     field,
+//!     payloads,
+//!     ParseResult,
+//!     Payload,
+//!     WormholeError,
+//! };
+//!
+//! #[derive(Debug, PartialEq)]
+//! pub struct Ping(pub u8);
+//!
+//! impl Payload for Ping {
+//!     const ID: u8 = 1;
+//!
+//!     fn parse(input: &[u8]) -> ParseResult<'_, Self> {
+//!         let (i, n) = field("n", 1, nom::number::complete::u8)(input)?;
+//!         Ok((i, Ping(n)))
+//!     }
+//!
+//!     fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
+//!         Ok(vec![self.0])
+//!     }
+//! }
+//!
+//! payloads! {
+//!     #[derive(Debug, PartialEq)]
+//!     pub enum Message {
+//!         Ping(Ping),
+//!     }
+//! }
+//!
+//! match Message::from_bytes([1, 7]).unwrap() {
+//!     Message::Ping(ping) => assert_eq!(ping, Ping(7)),
+//! }
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use nom::combinator::{
+    fail,
+    peek,
+    verify,
+};
+use nom::number::complete::u8;
+use nom::Finish;
+
+use crate::vaa::{
+    field,
+    ParseResult,
+};
+use crate::{
+    NomError,
+    ParseErrorKind,
+    WormholeError,
+};
+
+/// A payload identified by a leading payload ID byte.
+pub trait Payload: Sized {
+    const ID: u8;
+
+    /// Implement a nom parser for the Payload, starting after the payload ID.
+    fn parse(input: &[u8]) -> ParseResult<'_, Self>;
+
+    /// Serialize the Payload to Wormhole wire format, this does not include the payload ID.
+    #[cfg(feature = "alloc")]
+    fn serialize(&self) -> Result<Vec<u8>, WormholeError>;
+
+    /// Serialize a Payload along with its ID, this is the inverse of `from_bytes`.
+    #[cfg(feature = "alloc")]
+    fn to_bytes(&self) -> Result<Vec<u8>, WormholeError> {
+        let mut v = vec![Self::ID];
+        v.extend_from_slice(&self.serialize()?);
+        Ok(v)
+    }
+
+    /// Parse a Payload, failing if the ID does not match or if any bytes are left over.
+    fn from_bytes<T: AsRef<[u8]>>(input: T) -> Result<Self, WormholeError> {
+        parse_exact(input.as_ref(), parse_payload::<Self>)
+    }
+}
+
+/// Parse a Payload prefixed with its ID.
+pub fn parse_payload<P: Payload>(input: &[u8]) -> ParseResult<'_, P> {
+    let (i, _) = field("payload_id", 1, verify(u8, |&id| id == P::ID))(input)?;
+    P::parse(i)
+}
+
+/// Read the payload ID without consuming it.
+pub fn parse_payload_id(input: &[u8]) -> ParseResult<'_, u8> {
+    field("payload_id", 1, peek(u8))(input)
+}
+
+/// Fail on a payload ID that no parser accepted.
+pub fn unknown_payload_id<T>(input: &[u8]) -> ParseResult<'_, T> {
+    field("payload_id", 1, fail)(input)
+}
+
+/// Run `parser` over the whole of `input`, treating any bytes left over as an error. Payloads
+/// arrive inside VAAs whose length is fixed by the emitter, so leftover bytes mean the message was
+/// not the payload the caller expected.
+pub fn parse_exact<'a, T>(
+    input: &'a [u8],
+    mut parser: impl FnMut(&'a [u8]) -> ParseResult<'a, T>,
+) -> Result<T, WormholeError> {
+    let result = parser(input).and_then(|(i, value)| match i.len() {
+        0 => Ok((i, value)),
+        count => Err(nom::Err::Error(NomError {
+            input: i,
+            field: None,
+            kind:  ParseErrorKind::TrailingBytes { count },
+        })),
+    });
+
+    match result.finish() {
+        Ok((_, value)) => Ok(value),
+        Err(e) => Err(e.into_error(input)),
+    }
+}
+
+#[doc(hidden)]
+#[cfg(feature = "alloc")]
+pub mod __private {
+    pub use alloc::vec::Vec;
+}
+
+/// Implements `to_bytes` for an enum declared by `payloads!`. Whether serialization exists depends
+/// on the `alloc` feature of this crate rather than of the crate invoking `payloads!`, so the
+/// choice is made here.
+#[doc(hidden)]
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! __payloads_to_bytes {
+    ($name:ident, $($variant:ident),+) => {
+        /// Serialize the wrapped payload along with its ID.
+        pub fn to_bytes(
+            &self,
+        ) -> ::core::result::Result<$crate::payload::__private::Vec<u8>, $crate::WormholeError> {
+            match self {
+                $($name::$variant(payload) => $crate::Payload::to_bytes(payload),)+
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "alloc"))]
+#[macro_export]
+macro_rules! __payloads_to_bytes {
+    ($($tt:tt)*) => {};
+}
+
+/// Declare an enum over a set of `Payload` types, decoding to whichever variant matches the
+/// payload ID. Each variant must wrap a different payload, with IDs checked for uniqueness at
+/// compile time.
+///
+/// The enum gains `from_bytes`, which rejects unknown IDs and trailing bytes, `to_bytes` with the
+/// `alloc` feature, `id`, and a `From` conversion from each payload. See the module documentation for an example.
+#[macro_export]
+macro_rules! payloads {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident($payload:ty)),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($(#[$variant_meta])* $variant($payload),)+
+        }
+
+        const _: () = {
+            let ids = [$(<$payload as $crate::Payload>::ID),+];
+            let mut i = 0;
+            while i < ids.len() {
+                let mut j = i + 1;
+                while j < ids.len() {
+                    assert!(ids[i] != ids[j], "duplicate payload ID");
+                    j += 1;
+                }
+                i += 1;
+            }
+        };
+
+        impl $name {
+            /// The payload ID of the wrapped payload.
+            pub fn id(&self) -> u8 {
+                match self {
+                    $($name::$variant(_) => <$payload as $crate::Payload>::ID,)+
+                }
+            }
+
+            /// Implement a nom parser dispatching on the payload ID.
+            pub fn parse(input: &[u8]) -> $crate::ParseResult<'_, Self> {
+                let (_, id) = $crate::parse_payload_id(input)?;
+                $(
+                    if id == <$payload as $crate::Payload>::ID {
+                        let (i, payload) = $crate::parse_payload::<$payload>(input)?;
+                        return ::core::result::Result::Ok((i, $name::$variant(payload)));
+                    }
+                )+
+                $crate::unknown_payload_id(input)
+            }
+
+            /// Parse any of the payloads, failing on unknown IDs or if any bytes are left over.
+            pub fn from_bytes<T: ::core::convert::AsRef<[u8]>>(
+                input: T,
+            ) -> ::core::result::Result<Self, $crate::WormholeError> {
+                $crate::parse_exact(input.as_ref(), Self::parse)
+            }
+
+            $crate::__payloads_to_bytes!($name, $($variant),+);
+        }
+
+        $(
+            impl ::core::convert::From<$payload> for $name {
+                fn from(payload: $payload) -> Self {
+                    $name::$variant(payload)
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+mod testing {
+    use alloc::string::ToString;
+    use nom::number::complete::{
+        u32,
+        u8,
+    };
+    use nom::number::Endianness;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Ping {
+        nonce: u32,
+    }
+
+    impl Payload for Ping {
+        const ID: u8 = 1;
+
+        fn parse(input: &[u8]) -> ParseResult<'_, Self> {
+            let (i, nonce) = field("nonce", 4, u32(Endianness::Big))(input)?;
+            Ok((i, Ping { nonce }))
+        }
+
+        fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
+            Ok(self.nonce.to_be_bytes().to_vec())
+        }
+    }
+
+    // A second version of `Ping`, registered under a new ID.
+    #[derive(Debug, PartialEq)]
+    struct PingV2 {
+        nonce: u32,
+        ttl:   u8,
+    }
+
+    impl Payload for PingV2 {
+        const ID: u8 = 2;
+
+        fn parse(input: &[u8]) -> ParseResult<'_, Self> {
+            let (i, nonce) = field("nonce", 4, u32(Endianness::Big))(input)?;
+            let (i, ttl) = field("ttl", 1, u8)(i)?;
+            Ok((i, PingV2 { nonce, ttl }))
+        }
+
+        fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
+            let mut v = self.nonce.to_be_bytes().to_vec();
+            v.push(self.ttl);
+            Ok(v)
+        }
+    }
+
+    payloads! {
+        #[derive(Debug, PartialEq)]
+        enum Message {
+            Ping(Ping),
+            PingV2(PingV2),
+        }
+    }
+
+    #[test]
+    fn test_payload() {
+        let ping = Ping { nonce: 7 };
+        let bytes = ping.to_bytes().unwrap();
+        assert_eq!(bytes, [1, 0, 0, 0, 7]);
+        assert_eq!(Ping::from_bytes(&bytes).unwrap(), ping);
+
+        // Wrong ID.
+        assert_eq!(
+            Ping::from_bytes([2, 0, 0, 0, 7]).unwrap_err().to_string(),
+            "failed to parse payload_id at byte 0: invalid value"
+        );
+
+        // Trailing bytes.
+        assert_eq!(
+            Ping::from_bytes([1, 0, 0, 0, 7, 0, 0])
+                .unwrap_err()
+                .to_string(),
+            "failed to parse input at byte 5: 2 unexpected trailing bytes"
+        );
+    }
+
+    #[test]
+    fn test_payloads() {
+        let v1 = Message::from(Ping { nonce: 7 });
+        let v2 = Message::from(PingV2 { nonce: 7, ttl: 3 });
+        assert_eq!(v1.id(), 1);
+        assert_eq!(v2.id(), 2);
+
+        for message in [v1, v2] {
+            let bytes = message.to_bytes().unwrap();
+            assert_eq!(Message::from_bytes(&bytes).unwrap(), message);
+        }
+
+        match Message::from_bytes([2, 0, 0, 0, 7, 3]).unwrap() {
+            Message::PingV2(ping) => assert_eq!(ping.ttl, 3),
+            Message::Ping(_) => panic!("decoded the wrong version"),
+        }
+
+        // Unknown IDs and trailing bytes are rejected.
+        assert!(Message::from_bytes([3, 0, 0, 0, 7]).is_err());
+        assert!(matches!(
+            Message::from_bytes([1, 0, 0, 0, 7, 3]),
+            Err(WormholeError::ParseError(e))
+                if e.kind == ParseErrorKind::TrailingBytes { count: 1 }
+        ));
+    }
+}
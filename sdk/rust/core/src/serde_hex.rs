@@ -0,0 +1,87 @@
+//! Serde helpers for encoding byte strings as hex, for use with `#[serde(with = "...")]`. Wormhole
+//! addresses are opaque 32-byte values, so hex is the only encoding that reads the same for every
+//! chain. A leading `0x` is accepted when deserializing, but only produced for numbers.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use serde::de::Error;
+use serde::{
+    Deserialize,
+    Deserializer,
+    Serializer,
+};
+
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: AsRef<[u8]>,
+    S: Serializer,
+{
+    serializer.serialize_str(&hex::encode(bytes))
+}
+
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: TryFrom<Vec<u8>>,
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    decode(&s)
+}
+
+fn decode<T: TryFrom<Vec<u8>>, E: Error>(s: &str) -> Result<T, E> {
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    let bytes = hex::decode(s).map_err(E::custom)?;
+    let len = bytes.len();
+    T::try_from(bytes).map_err(|_| E::custom(format_args!("unexpected length {}", len)))
+}
+
+/// Encodes a `U256` as a `0x` prefixed hex number without leading zeros, the same encoding as the
+/// `serde` feature of `primitive-types`.
+pub mod u256 {
+    use super::*;
+    use primitive_types::U256;
+
+    pub fn serialize<S: Serializer>(value: &U256, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{:#x}", value))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let digits = s.strip_prefix("0x").unwrap_or(&s);
+        if digits.is_empty() || digits.len() > 64 {
+            return Err(D::Error::custom(format_args!("invalid U256 {}", s)));
+        }
+        U256::from_str_radix(digits, 16).map_err(D::Error::custom)
+    }
+}
+
+/// Encodes a sequence of byte strings, such as signatures, as a sequence of hex strings.
+pub mod seq {
+    use super::*;
+    use serde::ser::SerializeSeq;
+
+    pub fn serialize<T, S>(items: &[T], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(items.len()))?;
+        for item in items {
+            seq.serialize_element(&hex::encode(item))?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Vec<T>, D::Error>
+    where
+        T: TryFrom<Vec<u8>>,
+        D: Deserializer<'de>,
+    {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|s| decode(s))
+            .collect()
+    }
+}
@@ -5,23 +5,28 @@
 //! The core bridge does not define any general VAA's, thus all the payloads in this file are
 //! expected to require governance to be executed.
 
-use nom::multi::{
-    count,
-    fill,
-};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use nom::multi::fill;
 use nom::number::complete::{
     u32,
     u8,
 };
 use nom::number::Endianness;
-use nom::IResult;
 use primitive_types::U256;
 
 use crate::vaa::{
+    field,
     parse_fixed,
+    parse_fixed_count,
     GovernanceAction,
+    ParseResult,
 };
+use crate::WormholeError;
+use crate::WormholeError::SerializeFailed;
 
+#[derive(PartialEq, Debug)]
 pub struct GovernanceContractUpgrade {
     pub new_contract: [u8; 32],
 }
@@ -29,12 +34,17 @@ pub struct GovernanceContractUpgrade {
 impl GovernanceAction for GovernanceContractUpgrade {
     const MODULE: &'static [u8] = b"Core";
     const ACTION: u8 = 1;
-    fn parse(input: &[u8]) -> IResult<&[u8], Self> {
-        let (i, new_contract) = parse_fixed(input)?;
+    fn parse(input: &[u8]) -> ParseResult<'_, Self> {
+        let (i, new_contract) = field("new_contract", 32, parse_fixed)(input)?;
         Ok((i, Self { new_contract }))
     }
+
+    fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
+        Ok(self.new_contract.to_vec())
+    }
 }
 
+#[derive(PartialEq, Debug)]
 pub struct GovernanceGuardianSetChange {
     pub new_guardian_set_index: u32,
     pub new_guardian_set:       Vec<[u8; 20]>,
@@ -43,10 +53,12 @@ pub struct GovernanceGuardianSetChange {
 impl GovernanceAction for GovernanceGuardianSetChange {
     const MODULE: &'static [u8] = b"Core";
     const ACTION: u8 = 2;
-    fn parse(input: &[u8]) -> IResult<&[u8], Self> {
-        let (i, new_guardian_set_index) = u32(Endianness::Big)(input)?;
-        let (i, guardian_count) = u8(i)?;
-        let (i, new_guardian_set) = count(parse_fixed, guardian_count.into())(i)?;
+    fn parse(input: &[u8]) -> ParseResult<'_, Self> {
+        let (i, new_guardian_set_index) =
+            field("new_guardian_set_index", 4, u32(Endianness::Big))(input)?;
+        let (i, guardian_count) = field("guardian_count", 1, u8)(i)?;
+        let (i, new_guardian_set) =
+            parse_fixed_count("new_guardian_set", guardian_count.into())(i)?;
         Ok((
             i,
             Self {
@@ -55,8 +67,22 @@ impl GovernanceAction for GovernanceGuardianSetChange {
             },
         ))
     }
+
+    fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
+        // The guardian count is encoded as a single byte.
+        let guardian_count =
+            u8::try_from(self.new_guardian_set.len()).map_err(|_| SerializeFailed)?;
+        let mut v = Vec::with_capacity(5 + self.new_guardian_set.len() * 20);
+        v.extend_from_slice(&self.new_guardian_set_index.to_be_bytes());
+        v.push(guardian_count);
+        for guardian in &self.new_guardian_set {
+            v.extend_from_slice(guardian);
+        }
+        Ok(v)
+    }
 }
 
+#[derive(PartialEq, Debug)]
 pub struct GovernanceSetMessageFee {
     pub fee: U256,
 }
@@ -64,9 +90,9 @@ pub struct GovernanceSetMessageFee {
 impl GovernanceAction for GovernanceSetMessageFee {
     const MODULE: &'static [u8] = b"Core";
     const ACTION: u8 = 3;
-    fn parse(input: &[u8]) -> IResult<&[u8], Self> {
+    fn parse(input: &[u8]) -> ParseResult<'_, Self> {
         let mut fee = [0u8; 32];
-        let (i, _) = fill(u8, &mut fee)(input)?;
+        let (i, _) = field("fee", 32, fill(u8, &mut fee))(input)?;
         Ok((
             i,
             Self {
@@ -74,8 +100,15 @@ impl GovernanceAction for GovernanceSetMessageFee {
             },
         ))
     }
+
+    fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
+        let mut fee = [0u8; 32];
+        self.fee.to_big_endian(&mut fee);
+        Ok(fee.to_vec())
+    }
 }
 
+#[derive(PartialEq, Debug)]
 pub struct GovernanceTransferFees {
     pub amount: U256,
     pub to:     [u8; 32],
@@ -84,10 +117,10 @@ pub struct GovernanceTransferFees {
 impl GovernanceAction for GovernanceTransferFees {
     const MODULE: &'static [u8] = b"Core";
     const ACTION: u8 = 4;
-    fn parse(input: &[u8]) -> IResult<&[u8], Self> {
+    fn parse(input: &[u8]) -> ParseResult<'_, Self> {
         let mut amount = [0u8; 32];
-        let (i, _) = fill(u8, &mut amount)(input)?;
-        let (i, to) = parse_fixed(i)?;
+        let (i, _) = field("amount", 32, fill(u8, &mut amount))(input)?;
+        let (i, to) = field("to", 32, parse_fixed)(i)?;
         Ok((
             i,
             Self {
@@ -96,4 +129,11 @@ impl GovernanceAction for GovernanceTransferFees {
             },
         ))
     }
+
+    fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
+        let mut v = vec![0u8; 32];
+        self.amount.to_big_endian(&mut v);
+        v.extend_from_slice(&self.to);
+        Ok(v)
+    }
 }
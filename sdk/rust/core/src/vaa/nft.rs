@@ -3,25 +3,29 @@
 //! parsers can be used to read these VAAs. It also defines the Governance actions that this module
 //! supports, namely contract upgrades and chain registrations.
 
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::str::from_utf8;
 use nom::bytes::complete::take;
-use nom::combinator::verify;
-use nom::number::complete::u8;
-use nom::{
-    Finish,
-    IResult,
+use nom::combinator::{
+    map_opt,
+    verify,
 };
+use nom::number::complete::u8;
+use nom::Finish;
 use primitive_types::U256;
-use std::str::from_utf8;
 
 use crate::vaa::{
+    field,
     parse_chain,
     parse_fixed,
     GovernanceAction,
+    ParseResult,
+    ShortUTFString,
 };
-use crate::vaa::ShortUTFString;
 use crate::{
-    Chain,
     parse_fixed_utf8,
+    Chain,
     WormholeError,
 };
 
@@ -29,8 +33,14 @@ use crate::{
 /// that are attempting to initiate a transfer must lock up tokens in some manner, such as in a
 /// custody account or via burning, before emitting this message.
 #[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
 pub struct Transfer {
     /// Address of the token. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
     pub nft_address: [u8; 32],
 
     /// Chain ID of the token
@@ -43,12 +53,14 @@ pub struct Transfer {
     pub name: ShortUTFString,
 
     /// TokenID of the token (big-endian uint256)
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::u256"))]
     pub token_id: U256,
 
     /// URI of the token metadata
     pub uri: ShortUTFString,
 
     /// Address of the recipient. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
     pub to: [u8; 32],
 
     /// Chain ID of the recipient
@@ -59,29 +71,69 @@ impl Transfer {
     pub fn from_bytes<T: AsRef<[u8]>>(input: T) -> Result<Self, WormholeError> {
         match parse_payload_transfer(input.as_ref()).finish() {
             Ok(input) => Ok(input.1),
-            Err(e) => Err(WormholeError::ParseError(e.code as usize)),
+            Err(e) => Err(e.into_error(input.as_ref())),
         }
     }
 }
 
-fn parse_payload_transfer(input: &[u8]) -> IResult<&[u8], Transfer> {
+/// Hand written for the same reason as `token::Transfer`, the token ID is encoded little-endian.
+#[cfg(feature = "borsh")]
+impl borsh::BorshSerialize for Transfer {
+    fn serialize<W: borsh::maybestd::io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> borsh::maybestd::io::Result<()> {
+        self.nft_address.serialize(writer)?;
+        self.nft_chain.serialize(writer)?;
+        self.symbol.serialize(writer)?;
+        self.name.serialize(writer)?;
+        self.token_id.0.serialize(writer)?;
+        self.uri.serialize(writer)?;
+        self.to.serialize(writer)?;
+        self.to_chain.serialize(writer)
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshDeserialize for Transfer {
+    fn deserialize(buf: &mut &[u8]) -> borsh::maybestd::io::Result<Self> {
+        use borsh::BorshDeserialize;
+        Ok(Transfer {
+            nft_address: BorshDeserialize::deserialize(buf)?,
+            nft_chain:   BorshDeserialize::deserialize(buf)?,
+            symbol:      BorshDeserialize::deserialize(buf)?,
+            name:        BorshDeserialize::deserialize(buf)?,
+            token_id:    U256(BorshDeserialize::deserialize(buf)?),
+            uri:         BorshDeserialize::deserialize(buf)?,
+            to:          BorshDeserialize::deserialize(buf)?,
+            to_chain:    BorshDeserialize::deserialize(buf)?,
+        })
+    }
+}
+
+fn parse_payload_transfer(input: &[u8]) -> ParseResult<'_, Transfer> {
     // Parse Payload
-    let (i, _) = verify(u8, |&s| s == 0x1)(input.as_ref())?;
-    let (i, nft_address) = parse_fixed(i)?;
-    let (i, nft_chain) = parse_chain(i)?;
-    let (i, symbol): (_, [u8; 32]) = parse_fixed(i)?;
-    let (i, name): (_, [u8; 32]) = parse_fixed(i)?;
-    let (i, token_id): (_, [u8; 32]) = parse_fixed(i)?;
-    let (i, uri_len) = u8(i)?;
-    let (i, uri) = take(uri_len)(i)?;
-    let (i, to) = parse_fixed(i)?;
-    let (i, to_chain) = parse_chain(i)?;
-
-    // Name/Symbol and URI should be UTF-8 strings, attempt to parse the first two by removing
-    // invalid bytes -- for the latter, assume UTF-8 and fail if unparseable.
+    let (i, _) = field("payload_id", 1, verify(u8, |&s| s == 0x1))(input.as_ref())?;
+    let (i, nft_address) = field("nft_address", 32, parse_fixed)(i)?;
+    let (i, nft_chain) = field("nft_chain", 2, parse_chain)(i)?;
+    let (i, symbol): (_, [u8; 32]) = field("symbol", 32, parse_fixed)(i)?;
+    let (i, name): (_, [u8; 32]) = field("name", 32, parse_fixed)(i)?;
+    let (i, token_id): (_, [u8; 32]) = field("token_id", 32, parse_fixed)(i)?;
+    let (i, uri_len) = field("uri_len", 1, u8)(i)?;
+
+    // URI should be a UTF-8 string, assume UTF-8 and fail if unparseable.
+    let (i, uri) = field(
+        "uri",
+        uri_len.into(),
+        map_opt(take(uri_len), |uri| from_utf8(uri).ok()),
+    )(i)?;
+    let (i, to) = field("to", 32, parse_fixed)(i)?;
+    let (i, to_chain) = field("to_chain", 2, parse_chain)(i)?;
+
+    // Name/Symbol should be UTF-8 strings, attempt to parse them by removing invalid bytes.
     let name = parse_fixed_utf8::<_, 32>(name).unwrap();
     let symbol = parse_fixed_utf8::<_, 32>(symbol).unwrap();
-    let uri = from_utf8(uri).unwrap().to_string();
+    let uri = uri.to_string();
 
     Ok((
         i,
@@ -107,9 +159,9 @@ pub struct GovernanceRegisterChain {
 impl GovernanceAction for GovernanceRegisterChain {
     const MODULE: &'static [u8] = b"NFTBridge";
     const ACTION: u8 = 1;
-    fn parse(input: &[u8]) -> IResult<&[u8], Self> {
-        let (i, emitter) = parse_chain(input)?;
-        let (i, endpoint_address) = parse_fixed(i)?;
+    fn parse(input: &[u8]) -> ParseResult<'_, Self> {
+        let (i, emitter) = field("emitter", 2, parse_chain)(input)?;
+        let (i, endpoint_address) = field("endpoint_address", 32, parse_fixed)(i)?;
         Ok((
             i,
             Self {
@@ -118,6 +170,13 @@ impl GovernanceAction for GovernanceRegisterChain {
             },
         ))
     }
+
+    fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
+        let mut v = Vec::with_capacity(34);
+        v.extend_from_slice(&u16::from(self.emitter).to_be_bytes());
+        v.extend_from_slice(&self.endpoint_address);
+        Ok(v)
+    }
 }
 
 #[derive(PartialEq, Debug)]
@@ -128,8 +187,12 @@ pub struct GovernanceContractUpgrade {
 impl GovernanceAction for GovernanceContractUpgrade {
     const MODULE: &'static [u8] = b"NFTBridge";
     const ACTION: u8 = 2;
-    fn parse(input: &[u8]) -> IResult<&[u8], Self> {
-        let (i, new_contract) = parse_fixed(input)?;
+    fn parse(input: &[u8]) -> ParseResult<'_, Self> {
+        let (i, new_contract) = field("new_contract", 32, parse_fixed)(input)?;
         Ok((i, Self { new_contract }))
     }
+
+    fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
+        Ok(self.new_contract.to_vec())
+    }
 }
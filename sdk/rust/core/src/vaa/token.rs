@@ -3,19 +3,25 @@
 //! parsers can be used to read these VAAs. It also defines the Governance actions that this module
 //! supports, namely contract upgrades and chain registrations.
 
-use nom::combinator::verify;
+use alloc::vec::Vec;
+use nom::combinator::{
+    fail,
+    map,
+    peek,
+    rest,
+    verify,
+};
 use nom::multi::fill;
 use nom::number::complete::u8;
-use nom::{
-    Finish,
-    IResult,
-};
+use nom::Finish;
 use primitive_types::U256;
 
 use crate::vaa::{
-    GovernanceAction,
+    field,
     parse_chain,
     parse_fixed,
+    GovernanceAction,
+    ParseResult,
     ShortUTFString,
 };
 use crate::{
@@ -28,23 +34,32 @@ use crate::{
 /// that are attempting to initiate a transfer must lock up tokens in some manner, such as in a
 /// custody account or via burning, before emitting this message.
 #[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
 pub struct Transfer {
     /// Amount being transferred (big-endian uint256)
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::u256"))]
     pub amount: U256,
 
     /// Address of the token. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
     pub token_address: [u8; 32],
 
     /// Chain ID of the token
     pub token_chain: Chain,
 
     /// Address of the recipient. Left-zero-padded if shorter than 32 bytes
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
     pub to: [u8; 32],
 
     /// Chain ID of the recipient
     pub to_chain: Chain,
 
     /// Amount of tokens (big-endian uint256) that the user is willing to pay as relayer fee. Must be <= Amount.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::u256"))]
     pub fee: U256,
 }
 
@@ -52,24 +67,56 @@ impl Transfer {
     pub fn from_bytes<T: AsRef<[u8]>>(input: T) -> Result<Self, WormholeError> {
         match parse_payload_transfer(input.as_ref()).finish() {
             Ok(input) => Ok(input.1),
-            Err(e) => Err(WormholeError::ParseError(e.code as usize)),
+            Err(e) => Err(e.into_error(input.as_ref())),
         }
     }
 }
 
-fn parse_payload_transfer(input: &[u8]) -> IResult<&[u8], Transfer> {
+/// `U256` does not implement borsh, so the fields are written by hand. Amounts are encoded as
+/// 32 little-endian bytes, consistent with how borsh encodes its native integers.
+#[cfg(feature = "borsh")]
+impl borsh::BorshSerialize for Transfer {
+    fn serialize<W: borsh::maybestd::io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> borsh::maybestd::io::Result<()> {
+        self.amount.0.serialize(writer)?;
+        self.token_address.serialize(writer)?;
+        self.token_chain.serialize(writer)?;
+        self.to.serialize(writer)?;
+        self.to_chain.serialize(writer)?;
+        self.fee.0.serialize(writer)
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshDeserialize for Transfer {
+    fn deserialize(buf: &mut &[u8]) -> borsh::maybestd::io::Result<Self> {
+        use borsh::BorshDeserialize;
+        Ok(Transfer {
+            amount:        U256(BorshDeserialize::deserialize(buf)?),
+            token_address: BorshDeserialize::deserialize(buf)?,
+            token_chain:   BorshDeserialize::deserialize(buf)?,
+            to:            BorshDeserialize::deserialize(buf)?,
+            to_chain:      BorshDeserialize::deserialize(buf)?,
+            fee:           U256(BorshDeserialize::deserialize(buf)?),
+        })
+    }
+}
+
+fn parse_payload_transfer(input: &[u8]) -> ParseResult<'_, Transfer> {
     // Parser Buffers.
     let mut amount = [0u8; 32];
     let mut fee = [0u8; 32];
 
     // Parse Payload.
-    let (i, _) = verify(u8, |&s| s == 0x1)(input)?;
-    let (i, _) = fill(u8, &mut amount)(i)?;
-    let (i, token_address) = parse_fixed(i)?;
-    let (i, token_chain) = parse_chain(i)?;
-    let (i, to) = parse_fixed(i)?;
-    let (i, to_chain) = parse_chain(i)?;
-    let (i, _) = fill(u8, &mut fee)(i)?;
+    let (i, _) = field("payload_id", 1, verify(u8, |&s| s == 0x1))(input)?;
+    let (i, _) = field("amount", 32, fill(u8, &mut amount))(i)?;
+    let (i, token_address) = field("token_address", 32, parse_fixed)(i)?;
+    let (i, token_chain) = field("token_chain", 2, parse_chain)(i)?;
+    let (i, to) = field("to", 32, parse_fixed)(i)?;
+    let (i, to_chain) = field("to_chain", 2, parse_chain)(i)?;
+    let (i, _) = field("fee", 32, fill(u8, &mut fee))(i)?;
 
     Ok((
         i,
@@ -84,6 +131,70 @@ fn parse_payload_transfer(input: &[u8]) -> IResult<&[u8], Transfer> {
     ))
 }
 
+/// TransferWithPayload is a Transfer that additionally carries the sender and an arbitrary payload
+/// for the recipient to interpret. Only the recipient is able to redeem these transfers, allowing
+/// contracts to act on the payload atomically with receiving the tokens.
+#[derive(PartialEq, Debug, Clone)]
+pub struct TransferWithPayload {
+    /// Amount being transferred (big-endian uint256)
+    pub amount: U256,
+
+    /// Address of the token. Left-zero-padded if shorter than 32 bytes
+    pub token_address: [u8; 32],
+
+    /// Chain ID of the token
+    pub token_chain: Chain,
+
+    /// Address of the recipient. Left-zero-padded if shorter than 32 bytes
+    pub to: [u8; 32],
+
+    /// Chain ID of the recipient
+    pub to_chain: Chain,
+
+    /// Address of the sender. Left-zero-padded if shorter than 32 bytes
+    pub from_address: [u8; 32],
+
+    /// Arbitrary payload, this is everything after the fixed size fields.
+    pub payload: Vec<u8>,
+}
+
+impl TransferWithPayload {
+    pub fn from_bytes<T: AsRef<[u8]>>(input: T) -> Result<Self, WormholeError> {
+        match parse_payload_transfer_with_payload(input.as_ref()).finish() {
+            Ok(input) => Ok(input.1),
+            Err(e) => Err(e.into_error(input.as_ref())),
+        }
+    }
+}
+
+fn parse_payload_transfer_with_payload(input: &[u8]) -> ParseResult<'_, TransferWithPayload> {
+    // Parser Buffers.
+    let mut amount = [0u8; 32];
+
+    // Parse Payload.
+    let (i, _) = field("payload_id", 1, verify(u8, |&s| s == 0x3))(input)?;
+    let (i, _) = field("amount", 32, fill(u8, &mut amount))(i)?;
+    let (i, token_address) = field("token_address", 32, parse_fixed)(i)?;
+    let (i, token_chain) = field("token_chain", 2, parse_chain)(i)?;
+    let (i, to) = field("to", 32, parse_fixed)(i)?;
+    let (i, to_chain) = field("to_chain", 2, parse_chain)(i)?;
+    let (i, from_address) = field("from_address", 32, parse_fixed)(i)?;
+    let (i, payload) = rest(i)?;
+
+    Ok((
+        i,
+        TransferWithPayload {
+            amount: U256::from_big_endian(&amount),
+            token_address,
+            token_chain,
+            to,
+            to_chain,
+            from_address,
+            payload: payload.to_vec(),
+        },
+    ))
+}
+
 #[derive(PartialEq, Debug)]
 pub struct AssetMeta {
     /// Address of the original token on the source chain.
@@ -106,19 +217,19 @@ impl AssetMeta {
     pub fn from_bytes<T: AsRef<[u8]>>(input: T) -> Result<Self, WormholeError> {
         match parse_payload_asset_meta(input.as_ref()).finish() {
             Ok(input) => Ok(input.1),
-            Err(e) => Err(WormholeError::ParseError(e.code as usize)),
+            Err(e) => Err(e.into_error(input.as_ref())),
         }
     }
 }
 
-fn parse_payload_asset_meta(input: &[u8]) -> IResult<&[u8], AssetMeta> {
+fn parse_payload_asset_meta(input: &[u8]) -> ParseResult<'_, AssetMeta> {
     // Parse Payload.
-    let (i, _) = verify(u8, |&s| s == 0x2)(input.as_ref())?;
-    let (i, token_address) = parse_fixed(i)?;
-    let (i, token_chain) = parse_chain(i)?;
-    let (i, decimals) = u8(i)?;
-    let (i, symbol): (_, [u8; 32]) = parse_fixed(i)?;
-    let (i, name): (_, [u8; 32]) = parse_fixed(i)?;
+    let (i, _) = field("payload_id", 1, verify(u8, |&s| s == 0x2))(input.as_ref())?;
+    let (i, token_address) = field("token_address", 32, parse_fixed)(i)?;
+    let (i, token_chain) = field("token_chain", 2, parse_chain)(i)?;
+    let (i, decimals) = field("decimals", 1, u8)(i)?;
+    let (i, symbol): (_, [u8; 32]) = field("symbol", 32, parse_fixed)(i)?;
+    let (i, name): (_, [u8; 32]) = field("name", 32, parse_fixed)(i)?;
 
     // Name/Symbol should be UTF-8 strings, attempt to parse them by removing invalid bytes.
     let symbol = parse_fixed_utf8::<_, 32>(symbol).unwrap();
@@ -136,6 +247,38 @@ fn parse_payload_asset_meta(input: &[u8]) -> IResult<&[u8], AssetMeta> {
     ))
 }
 
+/// Any of the non-governance payloads emitted by the token bridge, distinguished by the leading
+/// payload ID byte.
+#[derive(PartialEq, Debug)]
+pub enum TokenBridgePayload {
+    Transfer(Transfer),
+    AssetMeta(AssetMeta),
+    TransferWithPayload(TransferWithPayload),
+}
+
+impl TokenBridgePayload {
+    /// Parse any token bridge payload, dispatching on the payload ID.
+    pub fn from_bytes<T: AsRef<[u8]>>(input: T) -> Result<Self, WormholeError> {
+        match parse_payload(input.as_ref()).finish() {
+            Ok(input) => Ok(input.1),
+            Err(e) => Err(e.into_error(input.as_ref())),
+        }
+    }
+}
+
+fn parse_payload(input: &[u8]) -> ParseResult<'_, TokenBridgePayload> {
+    let (_, payload_id) = field("payload_id", 1, peek(u8))(input)?;
+    match payload_id {
+        1 => map(parse_payload_transfer, TokenBridgePayload::Transfer)(input),
+        2 => map(parse_payload_asset_meta, TokenBridgePayload::AssetMeta)(input),
+        3 => map(
+            parse_payload_transfer_with_payload,
+            TokenBridgePayload::TransferWithPayload,
+        )(input),
+        _ => field("payload_id", 1, fail)(input),
+    }
+}
+
 #[derive(PartialEq, Debug)]
 pub struct GovernanceRegisterChain {
     pub emitter:          Chain,
@@ -145,9 +288,9 @@ pub struct GovernanceRegisterChain {
 impl GovernanceAction for GovernanceRegisterChain {
     const MODULE: &'static [u8] = b"TokenBridge";
     const ACTION: u8 = 1;
-    fn parse(input: &[u8]) -> IResult<&[u8], Self> {
-        let (i, emitter) = parse_chain(input)?;
-        let (i, endpoint_address) = parse_fixed(i)?;
+    fn parse(input: &[u8]) -> ParseResult<'_, Self> {
+        let (i, emitter) = field("emitter", 2, parse_chain)(input)?;
+        let (i, endpoint_address) = field("endpoint_address", 32, parse_fixed)(i)?;
         Ok((
             i,
             Self {
@@ -156,6 +299,13 @@ impl GovernanceAction for GovernanceRegisterChain {
             },
         ))
     }
+
+    fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
+        let mut v = Vec::with_capacity(34);
+        v.extend_from_slice(&u16::from(self.emitter).to_be_bytes());
+        v.extend_from_slice(&self.endpoint_address);
+        Ok(v)
+    }
 }
 
 #[derive(PartialEq, Debug)]
@@ -166,8 +316,12 @@ pub struct GovernanceContractUpgrade {
 impl GovernanceAction for GovernanceContractUpgrade {
     const MODULE: &'static [u8] = b"TokenBridge";
     const ACTION: u8 = 2;
-    fn parse(input: &[u8]) -> IResult<&[u8], Self> {
-        let (i, new_contract) = parse_fixed(input)?;
+    fn parse(input: &[u8]) -> ParseResult<'_, Self> {
+        let (i, new_contract) = field("new_contract", 32, parse_fixed)(input)?;
         Ok((i, Self { new_contract }))
     }
+
+    fn serialize(&self) -> Result<Vec<u8>, WormholeError> {
+        Ok(self.new_contract.to_vec())
+    }
 }
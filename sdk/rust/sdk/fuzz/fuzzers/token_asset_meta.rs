@@ -0,0 +1,7 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use wormhole_sdk::vaa::token::AssetMeta;
+
+fuzz_target!(|data: &[u8]| {
+    AssetMeta::from_bytes(data);
+});
@@ -0,0 +1,73 @@
+//! Parses the same input with both the SDK and the Solana token bridge, any disagreement between
+//! the two is a bug in one of them.
+//!
+//! The Solana parsers reject trailing bytes after fixed size payloads while the SDK ignores them,
+//! so results are only required to agree on acceptance when the input is exactly the payload size.
+
+#![no_main]
+use bridge::DeserializePayload;
+use libfuzzer_sys::fuzz_target;
+use token_bridge::messages::{
+    PayloadAssetMeta,
+    PayloadTransfer,
+    PayloadTransferWithPayload,
+};
+use wormhole_sdk::vaa::token::{
+    AssetMeta,
+    Transfer,
+    TransferWithPayload,
+};
+
+const TRANSFER_LEN: usize = 133;
+const ASSET_META_LEN: usize = 100;
+
+fuzz_target!(|data: &[u8]| {
+    let sdk = Transfer::from_bytes(data);
+    let solana = PayloadTransfer::deserialize(&mut &data[..]);
+    match (sdk, solana) {
+        (Ok(sdk), Ok(solana)) => {
+            assert_eq!(sdk.amount, solana.amount);
+            assert_eq!(sdk.token_address, solana.token_address);
+            assert_eq!(u16::from(sdk.token_chain), solana.token_chain);
+            assert_eq!(sdk.to, solana.to);
+            assert_eq!(u16::from(sdk.to_chain), solana.to_chain);
+            assert_eq!(sdk.fee, solana.fee);
+        }
+        (Ok(_), Err(_)) => assert_ne!(data.len(), TRANSFER_LEN),
+        (Err(_), Ok(_)) => panic!("only solana accepted transfer"),
+        (Err(_), Err(_)) => {}
+    }
+
+    let sdk = AssetMeta::from_bytes(data);
+    let solana = PayloadAssetMeta::deserialize(&mut &data[..]);
+    match (sdk, solana) {
+        (Ok(sdk), Ok(solana)) => {
+            assert_eq!(sdk.token_address, solana.token_address);
+            assert_eq!(u16::from(sdk.token_chain), solana.token_chain);
+            assert_eq!(sdk.decimals, solana.decimals);
+            assert_eq!(sdk.symbol, solana.symbol);
+            assert_eq!(sdk.name, solana.name);
+        }
+        (Ok(_), Err(_)) => assert_ne!(data.len(), ASSET_META_LEN),
+        (Err(_), Ok(_)) => panic!("only solana accepted asset meta"),
+        (Err(_), Err(_)) => {}
+    }
+
+    // Transfers with payload have no fixed size, so both must agree on every input.
+    let sdk = TransferWithPayload::from_bytes(data);
+    let solana = PayloadTransferWithPayload::deserialize(&mut &data[..]);
+    match (sdk, solana) {
+        (Ok(sdk), Ok(solana)) => {
+            assert_eq!(sdk.amount, solana.amount);
+            assert_eq!(sdk.token_address, solana.token_address);
+            assert_eq!(u16::from(sdk.token_chain), solana.token_chain);
+            assert_eq!(sdk.to, solana.to);
+            assert_eq!(u16::from(sdk.to_chain), solana.to_chain);
+            assert_eq!(sdk.from_address, solana.from_address);
+            assert_eq!(sdk.payload, solana.payload);
+        }
+        (Ok(_), Err(_)) => panic!("only the sdk accepted transfer with payload"),
+        (Err(_), Ok(_)) => panic!("only solana accepted transfer with payload"),
+        (Err(_), Err(_)) => {}
+    }
+});
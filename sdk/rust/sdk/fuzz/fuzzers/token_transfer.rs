@@ -0,0 +1,7 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use wormhole_sdk::vaa::token::Transfer;
+
+fuzz_target!(|data: &[u8]| {
+    Transfer::from_bytes(data);
+});
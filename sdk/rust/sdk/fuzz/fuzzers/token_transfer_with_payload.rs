@@ -0,0 +1,7 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use wormhole_sdk::vaa::token::TransferWithPayload;
+
+fuzz_target!(|data: &[u8]| {
+    TransferWithPayload::from_bytes(data);
+});
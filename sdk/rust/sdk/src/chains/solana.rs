@@ -3,6 +3,7 @@ use solana_program::pubkey::Pubkey;
 use solana_program::account_info::AccountInfo;
 use solana_program::entrypoint::ProgramResult;
 use solana_program::program::invoke_signed;
+use solana_program::program_error::ProgramError;
 use std::str::FromStr;
 
 // Export Bridge API
@@ -11,30 +12,32 @@ pub use bridge::BridgeData;
 pub use bridge::MessageData;
 pub use bridge::PostVAAData;
 pub use bridge::PostedVAAData;
+pub use bridge::SequenceTracker;
 pub use bridge::VerifySignaturesData;
 pub use bridge::instructions;
 pub use bridge::solitaire as bridge_entrypoint;
 pub use bridge::types::ConsistencyLevel;
 
+use wormhole_core::Chain;
 use wormhole_core::WormholeError;
 use wormhole_core::VAA;
 
-/// Export Core Mainnet Contract Address
-#[cfg(feature = "mainnet")]
-pub fn id() -> Pubkey {
-    Pubkey::from_str("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth").unwrap()
-}
+use crate::Network;
 
-/// Export Core Devnet Contract Address
-#[cfg(feature = "testnet")]
-pub fn id() -> Pubkey {
-    Pubkey::from_str("3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5").unwrap()
+pub mod token_bridge;
+
+/// Core Contract Address on the given network.
+pub fn id_for(network: Network) -> Pubkey {
+    let contracts = network
+        .contracts(Chain::Solana)
+        .expect("Solana contracts are deployed to every network");
+    Pubkey::from_str(contracts.core).unwrap()
 }
 
-/// Export Local Tilt Devnet Contract Address
-#[cfg(feature = "devnet")]
+/// Export Core Contract Address on the network selected by feature.
+#[cfg(any(feature = "mainnet", feature = "testnet", feature = "devnet"))]
 pub fn id() -> Pubkey {
-    Pubkey::from_str("Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o").unwrap()
+    id_for(crate::NETWORK)
 }
 
 /// Derives the Wormhole configuration account address.
@@ -77,10 +80,19 @@ pub fn read_vaa(vaa: &AccountInfo) -> Result<PostedVAAData, WormholeError> {
        .map_err(|_| WormholeError::DeserializeFailed)?)
 }
 
-/// This helper method wraps the steps required to invoke Wormhole, it takes care of fee payment,
+/// Deserialize helper for the next sequence number of an emitter from its sequence account.
+pub fn read_sequence(sequence: &AccountInfo) -> Result<u64, WormholeError> {
+    let tracker = SequenceTracker::try_from_slice(&sequence.data.borrow())
+        .map_err(|_| WormholeError::DeserializeFailed)?;
+    Ok(tracker.sequence)
+}
+
+/// This helper method wraps the steps required to invoke Wormhole on `network`, it takes care of fee payment,
 /// emitter derivation, and function invocation. This will be the right thing to use if you need to
 /// simply emit a message in the most straight forward way possible.
+#[allow(clippy::too_many_arguments)]
 pub fn post_message(
+    network: Network,
     program_id: Pubkey,
     payer: Pubkey,
     message: Pubkey,
@@ -90,18 +102,112 @@ pub fn post_message(
     accounts: &[AccountInfo],
     nonce: u32,
 ) -> ProgramResult {
+    try_post_message(
+        network,
+        program_id,
+        payer,
+        message,
+        payload,
+        consistency,
+        &["emitter".as_bytes()],
+        pda_seeds,
+        accounts,
+        nonce,
+    )?;
+
+    Ok(())
+}
+
+/// Like `post_message`, but emits from the PDA of `program_id` derived from `emitter_seeds`
+/// rather than `["emitter"]`, and returns the sequence number assigned to the message. Accounts
+/// missing from `accounts` are reported as `NotEnoughAccountKeys` rather than panicking.
+#[allow(clippy::too_many_arguments)]
+pub fn try_post_message(
+    network: Network,
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    payload: impl AsRef<[u8]>,
+    consistency: ConsistencyLevel,
+    emitter_seeds: &[&[u8]],
+    pda_seeds: Option<&[&[&[u8]]]>,
+    accounts: &[AccountInfo],
+    nonce: u32,
+) -> Result<u64, ProgramError> {
+    invoke_post_message(
+        network,
+        false,
+        program_id,
+        payer,
+        message,
+        payload,
+        consistency,
+        emitter_seeds,
+        pda_seeds,
+        accounts,
+        nonce,
+    )
+}
+
+/// Like `try_post_message`, but posts with `PostMessageUnreliable`. Unreliable messages are not
+/// guaranteed to be observed, in exchange the message account can be reused for later messages
+/// from the same emitter, so long as the payload is no larger than the original.
+#[allow(clippy::too_many_arguments)]
+pub fn try_post_message_unreliable(
+    network: Network,
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    payload: impl AsRef<[u8]>,
+    consistency: ConsistencyLevel,
+    emitter_seeds: &[&[u8]],
+    pda_seeds: Option<&[&[&[u8]]]>,
+    accounts: &[AccountInfo],
+    nonce: u32,
+) -> Result<u64, ProgramError> {
+    invoke_post_message(
+        network,
+        true,
+        program_id,
+        payer,
+        message,
+        payload,
+        consistency,
+        emitter_seeds,
+        pda_seeds,
+        accounts,
+        nonce,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn invoke_post_message(
+    network: Network,
+    unreliable: bool,
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    payload: impl AsRef<[u8]>,
+    consistency: ConsistencyLevel,
+    emitter_seeds: &[&[u8]],
+    pda_seeds: Option<&[&[&[u8]]]>,
+    accounts: &[AccountInfo],
+    nonce: u32,
+) -> Result<u64, ProgramError> {
     // Derive any necessary Pubkeys, derivation makes sure that we match the accounts that are being
     // provided by the user as well.
-    let id = id();
+    let id = id_for(network);
     let fee_collector = fee_collector(&id);
-    let (emitter, mut emitter_seeds, bump) = emitter(&program_id);
+    let (emitter, bump) = Pubkey::find_program_address(emitter_seeds, &program_id);
+    let sequence = sequence(&id, &emitter);
     let bump = &[bump];
+    let mut emitter_seeds = emitter_seeds.to_vec();
     emitter_seeds.push(bump);
 
     // Filter for the Config AccountInfo so we can access its data.
     let config = config(&id);
-    let config = accounts.iter().find(|item| *item.key == config).unwrap();
-    let config = read_config(config).unwrap();
+    let config = find_account(accounts, &config)?;
+    let config = read_config(config).map_err(|_| ProgramError::InvalidAccountData)?;
 
     let mut seeds = vec![&*emitter_seeds];
     if let Some(v) = pda_seeds {
@@ -120,8 +226,14 @@ pub fn post_message(
     )?;
 
     // Invoke the Wormhole post_message endpoint to create an on-chain message.
+    let instruction = if unreliable {
+        instructions::post_message_unreliable
+    } else {
+        instructions::post_message
+    };
+
     invoke_signed(
-        &instructions::post_message(
+        &instruction(
             id,
             payer,
             emitter,
@@ -129,11 +241,23 @@ pub fn post_message(
             nonce,
             payload.as_ref().to_vec(),
             consistency,
-        )
-        .unwrap(),
+        )?,
         accounts,
         &seeds
     )?;
 
-    Ok(())
+    // The sequence account now holds the sequence for the next message, ours is the one before.
+    let sequence = find_account(accounts, &sequence)?;
+    let sequence = read_sequence(sequence).map_err(|_| ProgramError::InvalidAccountData)?;
+    Ok(sequence - 1)
+}
+
+fn find_account<'a, 'b>(
+    accounts: &'a [AccountInfo<'b>],
+    key: &Pubkey,
+) -> Result<&'a AccountInfo<'b>, ProgramError> {
+    accounts
+        .iter()
+        .find(|item| item.key == key)
+        .ok_or(ProgramError::NotEnoughAccountKeys)
 }
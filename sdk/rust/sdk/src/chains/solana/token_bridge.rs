@@ -0,0 +1,373 @@
+//! Helpers for moving tokens through the Solana token bridge from another program. Transfers with
+//! payload record the program that sent them, and can only be redeemed by the program they are
+//! addressed to. On Solana a program proves its identity by signing with a PDA, `sender` when
+//! sending and `redeemer` when redeeming, these helpers derive those PDAs and add their seeds to
+//! any the caller passes in.
+
+use borsh::BorshDeserialize;
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program::invoke_signed;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use std::str::FromStr;
+
+// Export Token Bridge API
+pub use token_bridge::instructions;
+pub use token_bridge::messages::PayloadTransferWithPayload;
+pub use token_bridge::types::Config;
+pub use token_bridge::types::WrappedMeta;
+
+use bridge::DeserializePayload;
+use bridge::PostVAAData;
+use bridge::PostedVAAData;
+use token_bridge::CompleteNativeWithPayloadData;
+use token_bridge::CompleteWrappedWithPayloadData;
+use token_bridge::TransferNativeWithPayloadData;
+use token_bridge::TransferWrappedWithPayloadData;
+
+use wormhole_core::Chain;
+use wormhole_core::ForeignAddress;
+use wormhole_core::WormholeError;
+
+use crate::Network;
+
+/// Token Bridge Contract Address on the given network.
+pub fn id_for(network: Network) -> Pubkey {
+    let contracts = network
+        .contracts(Chain::Solana)
+        .expect("Solana contracts are deployed to every network");
+    Pubkey::from_str(contracts.token_bridge).unwrap()
+}
+
+/// Export Token Bridge Contract Address on the network selected by feature.
+#[cfg(any(feature = "mainnet", feature = "testnet", feature = "devnet"))]
+pub fn id() -> Pubkey {
+    id_for(crate::NETWORK)
+}
+
+/// Derives the token bridge configuration account address.
+pub fn config(id: &Pubkey) -> Pubkey {
+    let (config, _) = Pubkey::find_program_address(&[b"config"], id);
+    config
+}
+
+/// Derives the account holding custody of native tokens of the given mint while they are bridged.
+pub fn custody(id: &Pubkey, mint: &Pubkey) -> Pubkey {
+    let (custody, _) = Pubkey::find_program_address(&[&mint.to_bytes()], id);
+    custody
+}
+
+/// Derives the account the token bridge moves tokens with, it must be approved as a delegate of
+/// the source token account before a transfer.
+pub fn authority_signer(id: &Pubkey) -> Pubkey {
+    let (authority_signer, _) = Pubkey::find_program_address(&[b"authority_signer"], id);
+    authority_signer
+}
+
+/// Derives the account that owns all custody accounts.
+pub fn custody_signer(id: &Pubkey) -> Pubkey {
+    let (custody_signer, _) = Pubkey::find_program_address(&[b"custody_signer"], id);
+    custody_signer
+}
+
+/// Derives the mint authority of all wrapped tokens.
+pub fn mint_signer(id: &Pubkey) -> Pubkey {
+    let (mint_signer, _) = Pubkey::find_program_address(&[b"mint_signer"], id);
+    mint_signer
+}
+
+/// Derives the mint of the wrapped version of a token native to another chain.
+pub fn wrapped_mint(id: &Pubkey, token_chain: Chain, token_address: &ForeignAddress) -> Pubkey {
+    let token_chain = u16::from(token_chain).to_be_bytes();
+    let (mint, _) = Pubkey::find_program_address(&[b"wrapped", &token_chain, token_address], id);
+    mint
+}
+
+/// Derives the account recording the origin chain and address of a wrapped mint.
+pub fn wrapped_meta(id: &Pubkey, mint: &Pubkey) -> Pubkey {
+    let (meta, _) = Pubkey::find_program_address(&[b"meta", &mint.to_bytes()], id);
+    meta
+}
+
+/// Derives the claim account that marks a VAA as redeemed, preventing it being redeemed twice.
+pub fn claim(id: &Pubkey, vaa: &PostedVAAData) -> Pubkey {
+    let (claim, _) = Pubkey::find_program_address(
+        &[
+            &vaa.message.emitter_address,
+            &vaa.message.emitter_chain.to_be_bytes(),
+            &vaa.message.sequence.to_be_bytes(),
+        ],
+        id,
+    );
+    claim
+}
+
+/// Derives the sender PDA for a Solana program, the token bridge records `program_id` as the
+/// sender of a transfer with payload when this PDA signs for it.
+pub fn sender(program_id: &Pubkey) -> (Pubkey, Vec<&[u8]>, u8) {
+    let seeds = &["sender".as_bytes()];
+    let (sender, bump) = Pubkey::find_program_address(seeds, program_id);
+    (sender, seeds.to_vec(), bump)
+}
+
+/// Derives the redeemer PDA for a Solana program, transfers with payload addressed to
+/// `program_id` can only be redeemed when this PDA signs, and must be paid into a token account
+/// it owns.
+pub fn redeemer(program_id: &Pubkey) -> (Pubkey, Vec<&[u8]>, u8) {
+    let seeds = &["redeemer".as_bytes()];
+    let (redeemer, bump) = Pubkey::find_program_address(seeds, program_id);
+    (redeemer, seeds.to_vec(), bump)
+}
+
+/// Deserialize helper for the Config of a token bridge config account.
+pub fn read_config(config: &AccountInfo) -> Result<Config, WormholeError> {
+    Config::try_from_slice(&config.data.borrow()).map_err(|_| WormholeError::DeserializeFailed)
+}
+
+/// Deserialize helper for the WrappedMeta of a wrapped meta account.
+pub fn read_wrapped_meta(meta: &AccountInfo) -> Result<WrappedMeta, WormholeError> {
+    WrappedMeta::try_from_slice(&meta.data.borrow()).map_err(|_| WormholeError::DeserializeFailed)
+}
+
+/// Deserialize helper for the transfer carried by a posted token bridge VAA.
+pub fn read_transfer(vaa: &PostedVAAData) -> Result<PayloadTransferWithPayload, WormholeError> {
+    PayloadTransferWithPayload::deserialize(&mut &vaa.message.payload[..])
+        .map_err(|_| WormholeError::DeserializeFailed)
+}
+
+/// Transfer `amount` of a Solana native token to `recipient` on `recipient_chain`, along with an
+/// arbitrary payload. Tokens are taken from `from`, which `from_owner` must be able to sign for,
+/// either as a signer of the transaction or as a PDA of `program_id` with seeds in `pda_seeds`.
+/// The transfer is recorded as sent by `program_id`.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_native_with_payload(
+    network: Network,
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    from: Pubkey,
+    from_owner: Pubkey,
+    mint: Pubkey,
+    amount: u64,
+    recipient_chain: Chain,
+    recipient: ForeignAddress,
+    payload: impl AsRef<[u8]>,
+    pda_seeds: Option<&[&[&[u8]]]>,
+    accounts: &[AccountInfo],
+    nonce: u32,
+) -> ProgramResult {
+    let id = id_for(network);
+    let (_, mut sender_seeds, bump) = sender(&program_id);
+    let bump = &[bump];
+    sender_seeds.push(bump);
+
+    let mut seeds = vec![&*sender_seeds];
+    if let Some(v) = pda_seeds {
+        seeds.extend(v);
+    }
+
+    approve(&id, from, from_owner, amount, accounts, &seeds)?;
+
+    invoke_signed(
+        &instructions::transfer_native_with_payload(
+            id,
+            super::id_for(network),
+            payer,
+            message,
+            from,
+            mint,
+            TransferNativeWithPayloadData {
+                nonce,
+                amount,
+                target_address: recipient,
+                target_chain: recipient_chain.into(),
+                payload: payload.as_ref().to_vec(),
+                cpi_program_id: Some(program_id),
+            },
+        )?,
+        accounts,
+        &seeds,
+    )
+}
+
+/// Transfer `amount` of a wrapped token back to `recipient` on `recipient_chain`, along with an
+/// arbitrary payload. The wrapped mint is identified by the chain and address of the original
+/// token, and tokens are burned from `from` as in `transfer_native_with_payload`.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_wrapped_with_payload(
+    network: Network,
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    from: Pubkey,
+    from_owner: Pubkey,
+    token_chain: Chain,
+    token_address: ForeignAddress,
+    amount: u64,
+    recipient_chain: Chain,
+    recipient: ForeignAddress,
+    payload: impl AsRef<[u8]>,
+    pda_seeds: Option<&[&[&[u8]]]>,
+    accounts: &[AccountInfo],
+    nonce: u32,
+) -> ProgramResult {
+    let id = id_for(network);
+    let (_, mut sender_seeds, bump) = sender(&program_id);
+    let bump = &[bump];
+    sender_seeds.push(bump);
+
+    let mut seeds = vec![&*sender_seeds];
+    if let Some(v) = pda_seeds {
+        seeds.extend(v);
+    }
+
+    approve(&id, from, from_owner, amount, accounts, &seeds)?;
+
+    invoke_signed(
+        &instructions::transfer_wrapped_with_payload(
+            id,
+            super::id_for(network),
+            payer,
+            message,
+            from,
+            from_owner,
+            token_chain.into(),
+            token_address,
+            TransferWrappedWithPayloadData {
+                nonce,
+                amount,
+                target_address: recipient,
+                target_chain: recipient_chain.into(),
+                payload: payload.as_ref().to_vec(),
+                cpi_program_id: Some(program_id),
+            },
+        )?,
+        accounts,
+        &seeds,
+    )
+}
+
+/// Redeem a transfer with payload of a Solana native token addressed to `program_id`. `vaa` is the
+/// posted VAA account at `message`, read with `read_vaa`. Tokens are paid into `to`, which must be
+/// owned by the `redeemer` PDA of `program_id`, and any fee is paid to `fee_recipient`.
+#[allow(clippy::too_many_arguments)]
+pub fn complete_native_with_payload(
+    network: Network,
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    vaa: &PostedVAAData,
+    to: Pubkey,
+    fee_recipient: Option<Pubkey>,
+    mint: Pubkey,
+    pda_seeds: Option<&[&[&[u8]]]>,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let (redeemer, mut redeemer_seeds, bump) = redeemer(&program_id);
+    let bump = &[bump];
+    redeemer_seeds.push(bump);
+
+    let mut seeds = vec![&*redeemer_seeds];
+    if let Some(v) = pda_seeds {
+        seeds.extend(v);
+    }
+
+    invoke_signed(
+        &instructions::complete_native_with_payload(
+            id_for(network),
+            super::id_for(network),
+            payer,
+            message,
+            post_vaa_data(vaa),
+            to,
+            redeemer,
+            fee_recipient,
+            mint,
+            CompleteNativeWithPayloadData {},
+        )?,
+        accounts,
+        &seeds,
+    )
+}
+
+/// Redeem a transfer with payload of a wrapped token addressed to `program_id`, minting into `to`.
+/// Accounts are otherwise as for `complete_native_with_payload`.
+#[allow(clippy::too_many_arguments)]
+pub fn complete_wrapped_with_payload(
+    network: Network,
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    vaa: &PostedVAAData,
+    to: Pubkey,
+    fee_recipient: Option<Pubkey>,
+    pda_seeds: Option<&[&[&[u8]]]>,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let transfer = read_transfer(vaa).map_err(|_| ProgramError::InvalidAccountData)?;
+    let (redeemer, mut redeemer_seeds, bump) = redeemer(&program_id);
+    let bump = &[bump];
+    redeemer_seeds.push(bump);
+
+    let mut seeds = vec![&*redeemer_seeds];
+    if let Some(v) = pda_seeds {
+        seeds.extend(v);
+    }
+
+    invoke_signed(
+        &instructions::complete_wrapped_with_payload(
+            id_for(network),
+            super::id_for(network),
+            payer,
+            message,
+            post_vaa_data(vaa),
+            transfer,
+            to,
+            redeemer,
+            fee_recipient,
+            CompleteWrappedWithPayloadData {},
+        )?,
+        accounts,
+        &seeds,
+    )
+}
+
+/// Approve the token bridge to move `amount` out of `from` on behalf of `from_owner`.
+fn approve(
+    id: &Pubkey,
+    from: Pubkey,
+    from_owner: Pubkey,
+    amount: u64,
+    accounts: &[AccountInfo],
+    seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    invoke_signed(
+        &spl_token::instruction::approve(
+            &spl_token::id(),
+            &from,
+            &authority_signer(id),
+            &from_owner,
+            &[],
+            amount,
+        )?,
+        accounts,
+        seeds,
+    )
+}
+
+/// The token bridge instructions only read the body of a VAA to derive its claim account, so the
+/// fields a posted VAA no longer records are left empty.
+fn post_vaa_data(vaa: &PostedVAAData) -> PostVAAData {
+    PostVAAData {
+        version:            vaa.message.vaa_version,
+        guardian_set_index: 0,
+        timestamp:          vaa.message.vaa_time,
+        nonce:              vaa.message.nonce,
+        emitter_chain:      vaa.message.emitter_chain,
+        emitter_address:    vaa.message.emitter_address,
+        sequence:           vaa.message.sequence,
+        consistency_level:  vaa.message.consistency_level,
+        payload:            vaa.message.payload.clone(),
+    }
+}
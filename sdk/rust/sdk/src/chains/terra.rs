@@ -17,29 +17,33 @@ use wormhole::msg::{
     QueryMsg,
 };
 use wormhole::state::ParsedVAA;
+use wormhole_core::Chain;
 
-/// Export Core Mainnet Contract Address
-#[cfg(feature = "mainnet")]
-pub fn id() -> Addr {
-    Addr::unchecked("terra1dq03ugtd40zu9hcgdzrsq6z2z4hwhc9tqk2uy5")
-}
+use crate::Network;
 
-/// Export Core Devnet Contract Address
-#[cfg(feature = "testnet")]
-pub fn id() -> Addr {
-    Addr::unchecked("terra1pd65m0q9tl3v8znnz5f5ltsfegyzah7g42cx5v")
+pub mod token_bridge;
+
+/// Core Contract Address on the given network.
+pub fn id_for(network: Network) -> Addr {
+    let contracts = network
+        .contracts(Chain::Terra)
+        .expect("Terra contracts are deployed to every network");
+    Addr::unchecked(contracts.core)
 }
 
-/// Export Core Devnet Contract Address
-#[cfg(feature = "devnet")]
+/// Export Core Contract Address on the network selected by feature.
+#[cfg(any(feature = "mainnet", feature = "testnet", feature = "devnet"))]
 pub fn id() -> Addr {
-    Addr::unchecked("terra18vd8fpwxzck93qlwghaj6arh4p7c5n896xzem5")
+    id_for(crate::NETWORK)
 }
 
-pub fn post_message(nonce: u32, message: impl AsRef<[u8]>) -> StdResult<CosmosMsg>
-{
+pub fn post_message(
+    network: Network,
+    nonce: u32,
+    message: impl AsRef<[u8]>,
+) -> StdResult<CosmosMsg> {
     Ok(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: id().to_string(),
+        contract_addr: id_for(network).to_string(),
         funds:         vec![],
         msg:           to_binary(&ExecuteMsg::PostMessage {
             message: Binary::from(message.as_ref()),
@@ -50,12 +54,13 @@ pub fn post_message(nonce: u32, message: impl AsRef<[u8]>) -> StdResult<CosmosMs
 
 /// Parse a VAA using the Wormhole contract Query interface.
 pub fn parse_vaa(
+    network: Network,
     deps: DepsMut,
     env: Env,
     data: &Binary,
 ) -> StdResult<ParsedVAA> {
     let vaa: ParsedVAA = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-        contract_addr: id().to_string(),
+        contract_addr: id_for(network).to_string(),
         msg:           to_binary(&QueryMsg::VerifyVAA {
             vaa: data.clone(),
             block_time: env.block.time.seconds(),
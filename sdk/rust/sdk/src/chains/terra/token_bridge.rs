@@ -0,0 +1,148 @@
+//! Helpers for moving tokens through the Terra token bridge from another contract. Transfers need
+//! two messages: the bridge pulls CW20 tokens via an allowance, and native tokens must be deposited
+//! with the bridge before they can be sent. The `initiate_*` helpers return both messages in the
+//! order they must be executed.
+
+use cosmwasm_std::{
+    coins,
+    to_binary,
+    Addr,
+    Binary,
+    CosmosMsg,
+    Deps,
+    QueryRequest,
+    StdResult,
+    Uint128,
+    WasmMsg,
+    WasmQuery,
+};
+use cw20::Cw20ExecuteMsg;
+
+pub use terraswap::asset::{
+    Asset,
+    AssetInfo,
+};
+pub use token_bridge_terra::msg::TransferInfoResponse;
+
+use token_bridge_terra::msg::{
+    ExecuteMsg,
+    QueryMsg,
+};
+use wormhole_core::{
+    Chain,
+    ForeignAddress,
+};
+
+use crate::Network;
+
+/// Token Bridge Contract Address on the given network.
+pub fn id_for(network: Network) -> Addr {
+    let contracts = network
+        .contracts(Chain::Terra)
+        .expect("Terra contracts are deployed to every network");
+    Addr::unchecked(contracts.token_bridge)
+}
+
+/// Export Token Bridge Contract Address on the network selected by feature.
+#[cfg(any(feature = "mainnet", feature = "testnet", feature = "devnet"))]
+pub fn id() -> Addr {
+    id_for(crate::NETWORK)
+}
+
+/// Transfer `asset` to `recipient` on `recipient_chain`, paying `fee` out of the amount to whoever
+/// redeems the transfer.
+pub fn initiate_transfer(
+    network: Network,
+    asset: &Asset,
+    recipient_chain: Chain,
+    recipient: ForeignAddress,
+    fee: Uint128,
+    nonce: u32,
+) -> StdResult<Vec<CosmosMsg>> {
+    let transfer = ExecuteMsg::InitiateTransfer {
+        asset: asset.clone(),
+        recipient_chain: recipient_chain.into(),
+        recipient: Binary::from(&recipient[..]),
+        fee,
+        nonce,
+    };
+
+    Ok(vec![prepare(network, asset)?, execute(network, &transfer)?])
+}
+
+/// Transfer `asset` to `recipient` on `recipient_chain` along with an arbitrary payload. Only the
+/// recipient is able to redeem the transfer, via `complete_transfer_with_payload`.
+pub fn initiate_transfer_with_payload(
+    network: Network,
+    asset: &Asset,
+    recipient_chain: Chain,
+    recipient: ForeignAddress,
+    fee: Uint128,
+    payload: impl AsRef<[u8]>,
+    nonce: u32,
+) -> StdResult<Vec<CosmosMsg>> {
+    let transfer = ExecuteMsg::InitiateTransferWithPayload {
+        asset: asset.clone(),
+        recipient_chain: recipient_chain.into(),
+        recipient: Binary::from(&recipient[..]),
+        fee,
+        payload: Binary::from(payload.as_ref()),
+        nonce,
+    };
+
+    Ok(vec![prepare(network, asset)?, execute(network, &transfer)?])
+}
+
+/// Redeem a transfer with payload, the sender of this message must be the recipient named in the
+/// VAA. Any fee is paid to `relayer`.
+pub fn complete_transfer_with_payload(
+    network: Network,
+    vaa: &Binary,
+    relayer: &Addr,
+) -> StdResult<CosmosMsg> {
+    execute(network, &ExecuteMsg::CompleteTransferWithPayload {
+        data:    vaa.clone(),
+        relayer: relayer.to_string(),
+    })
+}
+
+/// Query the token bridge for the contents of a transfer VAA, including its payload.
+pub fn transfer_info(
+    network: Network,
+    deps: Deps,
+    vaa: &Binary,
+) -> StdResult<TransferInfoResponse> {
+    deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: id_for(network).to_string(),
+        msg:           to_binary(&QueryMsg::TransferInfo { vaa: vaa.clone() })?,
+    }))
+}
+
+/// The message that hands `asset` to the token bridge ahead of a transfer.
+fn prepare(network: Network, asset: &Asset) -> StdResult<CosmosMsg> {
+    Ok(match &asset.info {
+        AssetInfo::Token { contract_addr } => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.clone(),
+            funds:         vec![],
+            msg:           to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                spender: id_for(network).to_string(),
+                amount:  asset.amount,
+                expires: None,
+            })?,
+        }),
+
+        AssetInfo::NativeToken { denom } => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: id_for(network).to_string(),
+            funds:         coins(asset.amount.u128(), denom),
+            msg:           to_binary(&ExecuteMsg::DepositTokens {})?,
+        }),
+    })
+}
+
+fn execute(network: Network, msg: &ExecuteMsg) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: id_for(network).to_string(),
+        funds:         vec![],
+        msg:           to_binary(msg)?,
+    }))
+}
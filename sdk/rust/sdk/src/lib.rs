@@ -12,10 +12,28 @@
 //! Solana    | --feature=solana        | solana-sdk 1.7.1 
 //! Terra     | --feature=terra         | cosmos-sdk 0.16.0 
 //!
+//! Enabling `--feature=testing` adds a `testing` module with a mock guardian network for signing
+//! VAAs in integration tests.
+//!
+//! Helpers that talk to the Wormhole contracts take the `Network` to target. The `id` functions,
+//! and `NETWORK`, exist when one of `--feature=mainnet`, `testnet` or `devnet` picks a network at
+//! compile time, services that need to talk to several networks use the `id_for` functions instead.
+//!
 //! Docs specific to each blockchain's runtime can be found in submodules within the chains module
 //! at the root of this package.
 
 pub mod chains;
+pub mod network;
+
+#[cfg(feature = "testing")]
+pub mod testing;
 
 pub use wormhole_core::*;
 pub use chains::*;
+pub use network::{
+    Contracts,
+    Network,
+};
+
+#[cfg(any(feature = "mainnet", feature = "testnet", feature = "devnet"))]
+pub use network::NETWORK;
@@ -0,0 +1,168 @@
+//! Contract addresses for each Wormhole network. The `mainnet`, `testnet` and `devnet` features
+//! pick a single network at compile time for on-chain programs, services that talk to several
+//! networks from one binary can instead select a `Network` at runtime and look addresses up here.
+
+use std::fmt;
+use std::str::FromStr;
+
+use wormhole_core::{
+    Chain,
+    WormholeError,
+};
+
+#[cfg(any(
+    all(feature = "mainnet", feature = "testnet"),
+    all(feature = "mainnet", feature = "devnet"),
+    all(feature = "testnet", feature = "devnet"),
+))]
+compile_error!("only one of the `mainnet`, `testnet` and `devnet` features can be enabled");
+
+/// The network selected by the `mainnet`, `testnet` or `devnet` feature, which the `id` functions
+/// of each chain module target.
+#[cfg(feature = "mainnet")]
+pub const NETWORK: Network = Network::Mainnet;
+
+#[cfg(feature = "testnet")]
+pub const NETWORK: Network = Network::Testnet;
+
+#[cfg(feature = "devnet")]
+pub const NETWORK: Network = Network::Devnet;
+
+/// A deployment of the Wormhole contracts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    Devnet,
+}
+
+/// Addresses of the Wormhole contracts deployed to a single chain, in the chain's native format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Contracts {
+    pub core:         &'static str,
+    pub token_bridge: &'static str,
+}
+
+// (Network, Chain, Contracts)
+const CONTRACTS: [(Network, Chain, Contracts); 6] = [
+    (
+        Network::Mainnet,
+        Chain::Solana,
+        Contracts {
+            core:         "worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth",
+            token_bridge: "wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb",
+        },
+    ),
+    (
+        Network::Testnet,
+        Chain::Solana,
+        Contracts {
+            core:         "3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5",
+            token_bridge: "DZnkkTmCiFWfYTfT41X3Rd1kDgozqzxWaHqsw6W4x2oe",
+        },
+    ),
+    (
+        Network::Devnet,
+        Chain::Solana,
+        Contracts {
+            core:         "Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o",
+            token_bridge: "B6RHG3mfcckmrYN1UhmJzyS1XX3fZKbkeUcpJe9Sy3FE",
+        },
+    ),
+    (
+        Network::Mainnet,
+        Chain::Terra,
+        Contracts {
+            core:         "terra1dq03ugtd40zu9hcgdzrsq6z2z4hwhc9tqk2uy5",
+            token_bridge: "terra10nmmwe8r3g99a9newtqa7a75xfgs2e8z87r2sf",
+        },
+    ),
+    (
+        Network::Testnet,
+        Chain::Terra,
+        Contracts {
+            core:         "terra1pd65m0q9tl3v8znnz5f5ltsfegyzah7g42cx5v",
+            token_bridge: "terra1pseddrv0yfsn76u4zxrjmtf45kdlmalswdv39a",
+        },
+    ),
+    (
+        Network::Devnet,
+        Chain::Terra,
+        Contracts {
+            core:         "terra18vd8fpwxzck93qlwghaj6arh4p7c5n896xzem5",
+            token_bridge: "terra10pyejy66429refv3g35g2t7am0was7ya7kz2a4",
+        },
+    ),
+];
+
+impl Network {
+    pub fn name(self) -> &'static str {
+        match self {
+            Network::Mainnet => "mainnet",
+            Network::Testnet => "testnet",
+            Network::Devnet => "devnet",
+        }
+    }
+
+    /// The contracts deployed to `chain` on this network, or `None` if this library does not know
+    /// of a deployment there.
+    pub fn contracts(self, chain: Chain) -> Option<Contracts> {
+        CONTRACTS
+            .iter()
+            .find(|(network, c, _)| *network == self && *c == chain)
+            .map(|(_, _, contracts)| *contracts)
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Parses a case-insensitive network name.
+impl FromStr for Network {
+    type Err = WormholeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [Network::Mainnet, Network::Testnet, Network::Devnet]
+            .iter()
+            .find(|network| network.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or(WormholeError::InvalidNetwork)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_names() {
+        for network in [Network::Mainnet, Network::Testnet, Network::Devnet] {
+            assert_eq!(network.to_string().parse::<Network>().unwrap(), network);
+        }
+
+        assert_eq!("MainNet".parse::<Network>().unwrap(), Network::Mainnet);
+        assert!(matches!(
+            "localnet".parse::<Network>(),
+            Err(WormholeError::InvalidNetwork)
+        ));
+    }
+
+    #[test]
+    fn test_network_contracts() {
+        for network in [Network::Mainnet, Network::Testnet, Network::Devnet] {
+            for chain in [Chain::Solana, Chain::Terra] {
+                assert!(network.contracts(chain).is_some());
+            }
+            assert!(network.contracts(Chain::Ethereum).is_none());
+        }
+
+        // Addresses are distinct per network.
+        assert_ne!(
+            Network::Mainnet.contracts(Chain::Solana),
+            Network::Devnet.contracts(Chain::Solana)
+        );
+    }
+}
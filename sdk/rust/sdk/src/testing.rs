@@ -0,0 +1,328 @@
+//! Test helpers for contracts that consume VAAs. `MockGuardianSet` stands in for a guardian network,
+//! producing fully signed VAAs that pass the same checks as mainnet VAAs, along with knobs for
+//! producing the malformed VAAs a contract must reject.
+//!
+//! Keys are derived deterministically from the guardian set index, so the same set can be
+//! recreated on both sides of a test without passing keys around.
+//!
+//! ```rust
+//! use wormhole_sdk::testing::MockGuardianSet;
+//! use wormhole_sdk::{Chain, VAABuilder};
+//!
+//! let guardians = MockGuardianSet::new(0, 19);
+//! let body = VAABuilder::new()
+//!     .emitter_chain(Chain::Ethereum)
+//!     .sequence(1)
+//!     .payload(b"hello".to_vec())
+//!     .build();
+//!
+//! // Sign with exactly a quorum of guardians.
+//! let vaa = guardians.signer().quorum().sign(body);
+//! assert!(vaa.verify(&guardians.addresses()).is_ok());
+//! ```
+
+use libsecp256k1::{
+    Message,
+    PublicKey,
+    SecretKey,
+};
+use sha3::Digest;
+
+use wormhole_core::vaa::core::GovernanceGuardianSetChange;
+use wormhole_core::{
+    quorum,
+    Chain,
+    ForeignAddress,
+    GovernanceAction,
+    GuardianAddress,
+    Signature,
+    VAABuilder,
+    WormholeError,
+    VAA,
+};
+
+/// The chain governance VAAs are emitted from on mainnet.
+pub const GOVERNANCE_CHAIN: Chain = Chain::Solana;
+
+/// The emitter governance VAAs are emitted from on mainnet.
+pub const GOVERNANCE_EMITTER: ForeignAddress = {
+    let mut emitter = [0u8; 32];
+    emitter[31] = 4;
+    emitter
+};
+
+/// A set of guardian keys able to sign VAAs.
+pub struct MockGuardianSet {
+    index: u32,
+    keys:  Vec<SecretKey>,
+}
+
+impl MockGuardianSet {
+    /// Generate `n` guardian keys for the guardian set with the given index.
+    pub fn new(index: u32, n: u8) -> Self {
+        let keys = (0..n)
+            .map(|i| {
+                let mut h = sha3::Keccak256::default();
+                h.update(b"mock-guardian");
+                h.update(index.to_be_bytes());
+                h.update([i]);
+                SecretKey::parse(&h.finalize().into()).expect("hash is a valid secret key")
+            })
+            .collect();
+
+        Self { index, keys }
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// The number of signatures a VAA needs to be accepted by this set.
+    pub fn quorum(&self) -> usize {
+        quorum(self.keys.len())
+    }
+
+    pub fn secret_keys(&self) -> &[SecretKey] {
+        &self.keys
+    }
+
+    /// Ethereum style addresses of each guardian, in the form stored by the core contracts.
+    pub fn addresses(&self) -> Vec<GuardianAddress> {
+        self.keys
+            .iter()
+            .map(|key| {
+                let key = PublicKey::from_secret_key(key).serialize();
+                let hash = sha3::Keccak256::digest(&key[1..]);
+                let mut address = [0u8; 20];
+                address.copy_from_slice(&hash[12..]);
+                address
+            })
+            .collect()
+    }
+
+    /// Start signing a VAA, by default every guardian signs.
+    pub fn signer(&self) -> MockSigner<'_> {
+        MockSigner {
+            set:                self,
+            signers:            (0..self.keys.len() as u8).collect(),
+            corrupt:            Vec::new(),
+            misorder:           false,
+            guardian_set_index: self.index,
+        }
+    }
+
+    /// Sign a VAA with every guardian in the set.
+    pub fn sign(&self, vaa: VAA) -> VAA {
+        self.signer().sign(vaa)
+    }
+
+    /// Sign a governance action, emitted by the mainnet governance emitter, with every guardian.
+    pub fn governance<A: GovernanceAction>(
+        &self,
+        action: &A,
+        chain: Chain,
+        sequence: u64,
+    ) -> Result<VAA, WormholeError> {
+        let body = VAABuilder::new()
+            .emitter_chain(GOVERNANCE_CHAIN)
+            .emitter_address(GOVERNANCE_EMITTER)
+            .sequence(sequence)
+            .payload(action.to_bytes(chain)?)
+            .build();
+
+        Ok(self.sign(body))
+    }
+
+    /// Create the next guardian set with `n` guardians, along with the governance VAA signed by
+    /// this set that installs it. This set remains usable afterwards, which is useful for checking
+    /// that contracts reject VAAs signed by an expired set.
+    pub fn upgrade(&self, n: u8, sequence: u64) -> Result<(MockGuardianSet, VAA), WormholeError> {
+        let next = MockGuardianSet::new(self.index + 1, n);
+        let action = GovernanceGuardianSetChange {
+            new_guardian_set_index: next.index,
+            new_guardian_set:       next.addresses(),
+        };
+        let vaa = self.governance(&action, Chain::All, sequence)?;
+        Ok((next, vaa))
+    }
+}
+
+/// Configures which guardians sign a VAA, and how the resulting signatures are broken. Created
+/// with `MockGuardianSet::signer`.
+pub struct MockSigner<'a> {
+    set:                &'a MockGuardianSet,
+    signers:            Vec<u8>,
+    corrupt:            Vec<u8>,
+    misorder:           bool,
+    guardian_set_index: u32,
+}
+
+impl<'a> MockSigner<'a> {
+    /// Sign with the guardians at the given indices, in the order given. Indices outside the set
+    /// are written with a signature from guardian 0, so contracts see an invalid index.
+    pub fn signers(mut self, signers: &[u8]) -> Self {
+        self.signers = signers.to_vec();
+        self
+    }
+
+    /// Sign with only the first quorum of guardians.
+    pub fn quorum(mut self) -> Self {
+        self.signers.truncate(self.set.quorum());
+        self
+    }
+
+    /// Sign with one guardian fewer than quorum.
+    pub fn below_quorum(mut self) -> Self {
+        self.signers.truncate(self.set.quorum().saturating_sub(1));
+        self
+    }
+
+    /// Flip a bit in the signature of the given guardian, so it no longer recovers to their key.
+    pub fn corrupt(mut self, guardian: u8) -> Self {
+        self.corrupt.push(guardian);
+        self
+    }
+
+    /// Swap the first two signatures, breaking the ascending index order contracts require.
+    pub fn misorder(mut self) -> Self {
+        self.misorder = true;
+        self
+    }
+
+    /// Claim a guardian set index other than the one the keys belong to.
+    pub fn guardian_set_index(mut self, index: u32) -> Self {
+        self.guardian_set_index = index;
+        self
+    }
+
+    /// Sign the body of a VAA, replacing any existing signatures and guardian set index.
+    pub fn sign(self, mut vaa: VAA) -> VAA {
+        vaa.guardian_set_index = self.guardian_set_index;
+
+        let hash = vaa
+            .digest()
+            .expect("digest of an in-memory VAA never fails")
+            .secp256k_hash;
+        let message = Message::parse(&hash);
+
+        vaa.signatures = self
+            .signers
+            .iter()
+            .map(|&index| {
+                let key = self
+                    .set
+                    .keys
+                    .get(index as usize)
+                    .unwrap_or(&self.set.keys[0]);
+                let (signature, recovery_id) = libsecp256k1::sign(&message, key);
+                let mut out: Signature = [0u8; 66];
+                out[0] = index;
+                out[1..65].copy_from_slice(&signature.serialize());
+                out[65] = recovery_id.serialize();
+
+                // Flipping the low bit of `s` keeps the signature well formed.
+                if self.corrupt.contains(&index) {
+                    out[64] ^= 1;
+                }
+
+                out
+            })
+            .collect();
+
+        if self.misorder && vaa.signatures.len() > 1 {
+            vaa.signatures.swap(0, 1);
+        }
+
+        vaa
+    }
+
+    /// Sign the body of a VAA and encode it in the Wormhole wire format.
+    pub fn encode(self, vaa: VAA) -> Result<Vec<u8>, WormholeError> {
+        self.sign(vaa).serialize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body() -> VAA {
+        VAABuilder::new()
+            .emitter_chain(Chain::Ethereum)
+            .emitter_address([0xab; 32])
+            .sequence(7)
+            .payload(b"payload".to_vec())
+            .build()
+    }
+
+    #[test]
+    fn test_mock_guardian_set() {
+        let guardians = MockGuardianSet::new(0, 19);
+        let addresses = guardians.addresses();
+
+        // Keys are deterministic per index, and distinct between sets.
+        assert_eq!(MockGuardianSet::new(0, 19).addresses(), addresses);
+        assert_ne!(MockGuardianSet::new(1, 19).addresses(), addresses);
+
+        let vaa = guardians.sign(body());
+        assert_eq!(vaa.signatures.len(), 19);
+        assert!(vaa.verify(&addresses).is_ok());
+
+        let encoded = guardians.signer().quorum().encode(body()).unwrap();
+        let vaa = VAA::from_bytes(&encoded).unwrap();
+        assert_eq!(vaa.signatures.len(), 13);
+        assert!(vaa.verify(&addresses).is_ok());
+    }
+
+    #[test]
+    fn test_mock_guardian_set_faults() {
+        let guardians = MockGuardianSet::new(0, 19);
+        let addresses = guardians.addresses();
+
+        let vaa = guardians.signer().below_quorum().sign(body());
+        assert!(matches!(
+            vaa.verify(&addresses),
+            Err(WormholeError::NoQuorum)
+        ));
+
+        let vaa = guardians.signer().corrupt(3).sign(body());
+        assert!(matches!(
+            vaa.verify(&addresses),
+            Err(WormholeError::GuardianSignatureError)
+        ));
+
+        let vaa = guardians.signer().misorder().sign(body());
+        assert!(matches!(
+            vaa.verify(&addresses),
+            Err(WormholeError::WrongGuardianIndexOrder)
+        ));
+
+        let vaa = guardians.signer().guardian_set_index(5).sign(body());
+        assert_eq!(vaa.guardian_set_index, 5);
+    }
+
+    #[test]
+    fn test_mock_guardian_set_upgrade() {
+        let guardians = MockGuardianSet::new(0, 1);
+        let (next, vaa) = guardians.upgrade(7, 1).unwrap();
+        assert_eq!(next.index(), 1);
+        assert_eq!(vaa.emitter_chain, GOVERNANCE_CHAIN);
+        assert_eq!(vaa.emitter_address, GOVERNANCE_EMITTER);
+        assert!(vaa.verify(&guardians.addresses()).is_ok());
+
+        let (_, action) = GovernanceGuardianSetChange::from_bytes(&vaa.payload, None).unwrap();
+        assert_eq!(action.new_guardian_set_index, 1);
+        assert_eq!(action.new_guardian_set, next.addresses());
+
+        // VAAs from the old set still carry the old index.
+        assert_eq!(guardians.sign(body()).guardian_set_index, 0);
+    }
+}
@@ -0,0 +1,434 @@
+//! Decode VAAs into a form humans can read. `inspect` breaks a VAA down into its header,
+//! signatures, body, digest and decoded payload as a JSON value, which `table` can flatten into one
+//! line per field for reading in a terminal.
+//!
+//! Payloads are decoded with the parsers in `wormhole_core`. A payload carries no marker for the
+//! module that emitted it, so known governance actions are tried first, then the token and NFT
+//! bridge payloads by payload ID. Anything else is left as the raw hex in the body.
+
+use std::fmt;
+
+use serde_json::{
+    json,
+    Map,
+    Value,
+};
+use wormhole_core::vaa::{
+    nft,
+    token,
+};
+use wormhole_core::{
+    recover_guardian,
+    Chain,
+    GovHeader,
+    GovernancePayload,
+    GuardianAddress,
+    WormholeError,
+    VAA,
+};
+
+#[derive(Debug)]
+pub enum Error {
+    /// The input was neither hex nor base64.
+    InvalidEncoding,
+    Wormhole(WormholeError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidEncoding => f.write_str("VAA is neither hex nor base64"),
+            Error::Wormhole(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::InvalidEncoding => None,
+            Error::Wormhole(e) => Some(e),
+        }
+    }
+}
+
+impl From<WormholeError> for Error {
+    fn from(other: WormholeError) -> Self {
+        Error::Wormhole(other)
+    }
+}
+
+/// Decode a VAA given as hex, with or without a `0x` prefix, or as base64 as served by the guardian
+/// REST API. Surrounding whitespace is ignored.
+pub fn decode(input: &str) -> Result<Vec<u8>, Error> {
+    let input = input.trim();
+    let hex = input.strip_prefix("0x").unwrap_or(input);
+    hex::decode(hex)
+        .or_else(|_| base64::decode(input))
+        .map_err(|_| Error::InvalidEncoding)
+}
+
+/// Parse a guardian set given as comma separated hex addresses.
+pub fn parse_guardian_set(input: &str) -> Result<Vec<GuardianAddress>, Error> {
+    input
+        .split(',')
+        .map(|address| {
+            let address = address.trim();
+            let address = address.strip_prefix("0x").unwrap_or(address);
+            let mut bytes = [0u8; 20];
+            hex::decode_to_slice(address, &mut bytes).map_err(|_| WormholeError::InvalidAddress)?;
+            Ok(bytes)
+        })
+        .collect()
+}
+
+/// Break a VAA down into its parts. Each signature is shown with the address of the guardian it
+/// recovers to, and when a `guardian_set` is given, whether that matches the guardian at the
+/// signature's index along with the result of verifying the whole VAA against the set.
+pub fn inspect(vaa: &[u8], guardian_set: Option<&[GuardianAddress]>) -> Result<Value, Error> {
+    let vaa = VAA::from_bytes(vaa)?;
+    let digest = vaa.digest().ok_or(WormholeError::SerializeFailed)?;
+
+    let signatures: Vec<Value> = vaa
+        .signatures
+        .iter()
+        .map(|signature| {
+            let index = signature[0];
+            let mut out = Map::new();
+            out.insert("index".into(), index.into());
+            out.insert("signature".into(), hex::encode(&signature[1..]).into());
+
+            let recovered = recover_guardian(signature, &digest.secp256k_hash);
+            let guardian = match &recovered {
+                Ok(address) => hex::encode(address).into(),
+                Err(e) => format!("error: {}", e).into(),
+            };
+            out.insert("guardian".into(), guardian);
+
+            if let Some(set) = guardian_set {
+                let expected = set.get(index as usize);
+                let valid = matches!((&recovered, expected), (Ok(a), Some(b)) if a == b);
+                out.insert("valid".into(), valid.into());
+            }
+
+            Value::Object(out)
+        })
+        .collect();
+
+    let mut out = json!({
+        "header": {
+            "version": vaa.version,
+            "guardian_set_index": vaa.guardian_set_index,
+            "signatures": signatures,
+        },
+        "body": {
+            "timestamp": vaa.timestamp,
+            "nonce": vaa.nonce,
+            "emitter_chain": chain(vaa.emitter_chain),
+            "emitter_address": hex::encode(vaa.emitter_address),
+            "sequence": vaa.sequence,
+            "consistency_level": vaa.consistency_level,
+            "payload": hex::encode(&vaa.payload),
+        },
+        "digest": {
+            "hash": hex::encode(digest.hash),
+            "secp256k_hash": hex::encode(digest.secp256k_hash),
+        },
+        "payload": decode_payload(&vaa.payload),
+    });
+
+    if let Some(set) = guardian_set {
+        let verified = match vaa.verify(set) {
+            Ok(()) => "ok".to_string(),
+            Err(e) => e.to_string(),
+        };
+        out.as_object_mut()
+            .unwrap()
+            .insert("verified".into(), verified.into());
+    }
+
+    Ok(out)
+}
+
+/// Decode a payload with whichever parser accepts it, or `null` if none do.
+pub fn decode_payload(payload: &[u8]) -> Value {
+    if let Ok((header, action)) = GovernancePayload::from_bytes(payload, None) {
+        if let Some(value) = governance(&header, action) {
+            return value;
+        }
+    }
+
+    match payload.first() {
+        // Token and NFT transfers share a payload ID. Token transfers are fixed size, while NFT
+        // transfers are longer, so the token parser would accept them with bytes left over.
+        Some(1) if payload.len() == 133 => match token::Transfer::from_bytes(payload) {
+            Ok(t) => json!({
+                "type": "token_transfer",
+                "amount": t.amount.to_string(),
+                "token_address": hex::encode(t.token_address),
+                "token_chain": chain(t.token_chain),
+                "to": hex::encode(t.to),
+                "to_chain": chain(t.to_chain),
+                "fee": t.fee.to_string(),
+            }),
+            Err(_) => Value::Null,
+        },
+
+        Some(1) => match nft::Transfer::from_bytes(payload) {
+            Ok(t) => json!({
+                "type": "nft_transfer",
+                "nft_address": hex::encode(t.nft_address),
+                "nft_chain": chain(t.nft_chain),
+                "symbol": t.symbol,
+                "name": t.name,
+                "token_id": t.token_id.to_string(),
+                "uri": t.uri,
+                "to": hex::encode(t.to),
+                "to_chain": chain(t.to_chain),
+            }),
+            Err(_) => Value::Null,
+        },
+
+        Some(2) => match token::AssetMeta::from_bytes(payload) {
+            Ok(m) => json!({
+                "type": "asset_meta",
+                "token_address": hex::encode(m.token_address),
+                "token_chain": chain(m.token_chain),
+                "decimals": m.decimals,
+                "symbol": m.symbol,
+                "name": m.name,
+            }),
+            Err(_) => Value::Null,
+        },
+
+        Some(3) => match token::TransferWithPayload::from_bytes(payload) {
+            Ok(t) => json!({
+                "type": "token_transfer_with_payload",
+                "amount": t.amount.to_string(),
+                "token_address": hex::encode(t.token_address),
+                "token_chain": chain(t.token_chain),
+                "to": hex::encode(t.to),
+                "to_chain": chain(t.to_chain),
+                "from_address": hex::encode(t.from_address),
+                "payload": hex::encode(&t.payload),
+            }),
+            Err(_) => Value::Null,
+        },
+
+        _ => Value::Null,
+    }
+}
+
+/// Governance actions for modules this library does not know of are not decoded, as any payload
+/// long enough to hold a governance header would otherwise be shown as one.
+fn governance(header: &GovHeader, action: GovernancePayload) -> Option<Value> {
+    use GovernancePayload::*;
+
+    let (name, fields) = match action {
+        CoreContractUpgrade(a) => (
+            "contract_upgrade",
+            json!({ "new_contract": hex::encode(a.new_contract) }),
+        ),
+        CoreGuardianSetChange(a) => (
+            "guardian_set_change",
+            json!({
+                "new_guardian_set_index": a.new_guardian_set_index,
+                "new_guardian_set": a.new_guardian_set.iter().map(hex::encode).collect::<Vec<_>>(),
+            }),
+        ),
+        CoreSetMessageFee(a) => ("set_message_fee", json!({ "fee": a.fee.to_string() })),
+        CoreTransferFees(a) => (
+            "transfer_fees",
+            json!({ "amount": a.amount.to_string(), "to": hex::encode(a.to) }),
+        ),
+        TokenBridgeRegisterChain(token::GovernanceRegisterChain {
+            emitter,
+            endpoint_address,
+        })
+        | NFTBridgeRegisterChain(nft::GovernanceRegisterChain {
+            emitter,
+            endpoint_address,
+        }) => (
+            "register_chain",
+            json!({
+                "emitter_chain": chain(emitter),
+                "endpoint_address": hex::encode(endpoint_address),
+            }),
+        ),
+        TokenBridgeContractUpgrade(token::GovernanceContractUpgrade { new_contract })
+        | NFTBridgeContractUpgrade(nft::GovernanceContractUpgrade { new_contract }) => (
+            "contract_upgrade",
+            json!({ "new_contract": hex::encode(new_contract) }),
+        ),
+        Unknown { .. } => return None,
+    };
+
+    // Module names are left-padded ASCII, such as `Core` or `TokenBridge`.
+    let module: String = header
+        .module
+        .iter()
+        .filter(|&&b| b != 0)
+        .map(|&b| b as char)
+        .collect();
+
+    let mut out = json!({
+        "type": "governance",
+        "module": module,
+        "action": name,
+        "target_chain": chain(header.chains),
+    });
+    out.as_object_mut()
+        .unwrap()
+        .extend(fields.as_object().unwrap().clone());
+    Some(out)
+}
+
+/// Chains are shown by name alongside their ID, as the ID is what appears on the wire.
+fn chain(chain: Chain) -> String {
+    match chain.name() {
+        Some(name) => format!("{} ({})", name, u16::from(chain)),
+        None => u16::from(chain).to_string(),
+    }
+}
+
+/// Flatten the output of `inspect` into aligned `key value` lines, naming nested fields by their
+/// path, such as `header.signatures[0].guardian`.
+pub fn table(value: &Value) -> String {
+    let mut rows = Vec::new();
+    flatten(String::new(), value, &mut rows);
+
+    let width = rows.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+    rows.iter()
+        .map(|(key, value)| format!("{:width$}  {}\n", key, value, width = width))
+        .collect()
+}
+
+fn flatten(path: String, value: &Value, rows: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let path = match path.is_empty() {
+                    true => key.clone(),
+                    false => format!("{}.{}", path, key),
+                };
+                flatten(path, value, rows);
+            }
+        }
+        Value::Array(values) => {
+            for (i, value) in values.iter().enumerate() {
+                flatten(format!("{}[{}]", path, i), value, rows);
+            }
+        }
+        Value::String(s) => rows.push((path, s.clone())),
+        value => rows.push((path, value.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libsecp256k1::{
+        Message,
+        PublicKey,
+        SecretKey,
+    };
+    use wormhole_core::vaa::core::GovernanceGuardianSetChange;
+    use wormhole_core::{
+        GovernanceAction,
+        VAABuilder,
+    };
+
+    fn guardian(seed: u8) -> (SecretKey, GuardianAddress) {
+        use sha3::Digest;
+
+        let key = SecretKey::parse(&[seed; 32]).unwrap();
+        let public = PublicKey::from_secret_key(&key).serialize();
+        let hash = sha3::Keccak256::digest(&public[1..]);
+        let mut address = [0u8; 20];
+        address.copy_from_slice(&hash[12..]);
+        (key, address)
+    }
+
+    fn sign(mut vaa: VAA, keys: &[SecretKey]) -> Vec<u8> {
+        let message = Message::parse(&vaa.digest().unwrap().secp256k_hash);
+        vaa.signatures = keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| {
+                let (signature, recovery_id) = libsecp256k1::sign(&message, key);
+                let mut out = [0u8; 66];
+                out[0] = i as u8;
+                out[1..65].copy_from_slice(&signature.serialize());
+                out[65] = recovery_id.serialize();
+                out
+            })
+            .collect();
+        vaa.serialize().unwrap()
+    }
+
+    #[test]
+    fn test_decode() {
+        let bytes = vec![0x01, 0x02, 0xff];
+        assert_eq!(decode("0102ff").unwrap(), bytes);
+        assert_eq!(decode(" 0x0102ff\n").unwrap(), bytes);
+        assert_eq!(decode("AQL/").unwrap(), bytes);
+        assert!(matches!(decode("not a vaa!"), Err(Error::InvalidEncoding)));
+    }
+
+    #[test]
+    fn test_inspect_governance() {
+        let (key, address) = guardian(1);
+        let action = GovernanceGuardianSetChange {
+            new_guardian_set_index: 1,
+            new_guardian_set:       vec![address],
+        };
+        let body = VAABuilder::new()
+            .emitter_chain(Chain::Solana)
+            .sequence(3)
+            .payload(action.to_bytes(Chain::All).unwrap())
+            .build();
+        let encoded = sign(body, &[key]);
+
+        let out = inspect(&encoded, Some(&[address])).unwrap();
+        assert_eq!(out["verified"], "ok");
+        assert_eq!(out["body"]["sequence"], 3);
+        assert_eq!(
+            out["header"]["signatures"][0]["guardian"],
+            hex::encode(address)
+        );
+        assert_eq!(out["header"]["signatures"][0]["valid"], true);
+        assert_eq!(out["payload"]["module"], "Core");
+        assert_eq!(out["payload"]["action"], "guardian_set_change");
+        assert_eq!(out["payload"]["new_guardian_set"][0], hex::encode(address));
+
+        // Against the wrong set the signature still recovers, but no longer matches.
+        let (_, other) = guardian(2);
+        let out = inspect(&encoded, Some(&[other])).unwrap();
+        assert_eq!(out["header"]["signatures"][0]["valid"], false);
+        assert_ne!(out["verified"], "ok");
+
+        let table = table(&out);
+        assert!(table.contains("header.signatures[0].guardian"));
+        assert!(table.contains("payload.new_guardian_set[0]"));
+    }
+
+    #[test]
+    fn test_decode_payload() {
+        let mut transfer = vec![1u8];
+        transfer.extend_from_slice(&[0u8; 31]);
+        transfer.push(100);
+        transfer.extend_from_slice(&[0xaa; 32]);
+        transfer.extend_from_slice(&2u16.to_be_bytes());
+        transfer.extend_from_slice(&[0xbb; 32]);
+        transfer.extend_from_slice(&1u16.to_be_bytes());
+        transfer.extend_from_slice(&[0u8; 32]);
+        assert_eq!(transfer.len(), 133);
+
+        let out = decode_payload(&transfer);
+        assert_eq!(out["type"], "token_transfer");
+        assert_eq!(out["amount"], "100");
+        assert_eq!(out["token_chain"], "ethereum (2)");
+
+        assert_eq!(decode_payload(b"hello"), Value::Null);
+    }
+}
@@ -0,0 +1,66 @@
+//! Print the contents of a VAA.
+//!
+//! ```text
+//! wormhole-vaa [--json | --table] [--guardians <addr,addr,...>] [VAA]
+//! ```
+//!
+//! The VAA is given as hex or base64, and is read from stdin when not passed as an argument.
+
+use std::io::Read;
+use std::process;
+
+const USAGE: &str = "usage: wormhole-vaa [--json | --table] [--guardians <addr,addr,...>] [VAA]";
+
+enum Format {
+    Json,
+    Table,
+}
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let mut format = Format::Table;
+    let mut guardians = None;
+    let mut input = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => format = Format::Json,
+            "--table" => format = Format::Table,
+            "--guardians" => {
+                let set = args.next().ok_or(USAGE)?;
+                guardians = Some(wormhole_vaa::parse_guardian_set(&set)?);
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ if input.is_none() && !arg.starts_with("--") => input = Some(arg),
+            _ => return Err(USAGE.into()),
+        }
+    }
+
+    let input = match input {
+        Some(input) => input,
+        None => {
+            let mut buffer = String::new();
+            let _ = std::io::stdin().read_to_string(&mut buffer)?;
+            buffer
+        }
+    };
+
+    let vaa = wormhole_vaa::decode(&input)?;
+    let out = wormhole_vaa::inspect(&vaa, guardians.as_deref())?;
+    match format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(&out)?),
+        Format::Table => print!("{}", wormhole_vaa::table(&out)),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+//! Parse the structured events logged by the bridge out of a transaction's log messages.
+//!
+//! Only events logged by the bridge program itself are returned, whether it was invoked directly
+//! or through a cross-program invocation. Other programs can log the same text, so the invocation
+//! stack is tracked to tell them apart. Logs of failed transactions still contain the events of
+//! the instructions that ran, so callers should only parse logs of successful transactions.
+
+pub use bridge::events::Event;
+use solana_program::pubkey::Pubkey;
+use std::str::FromStr;
+
+const PROGRAM_LOG: &str = "Program log: ";
+
+/// Parse every bridge event out of a transaction's log messages, in the order they were logged.
+pub fn parse_events(bridge: &Pubkey, logs: &[String]) -> Vec<Event> {
+    let mut stack: Vec<Pubkey> = Vec::new();
+    let mut events = Vec::new();
+
+    for line in logs {
+        if let Some(message) = line.strip_prefix(PROGRAM_LOG) {
+            if stack.last() == Some(bridge) {
+                events.extend(Event::from_log(message));
+            }
+            continue;
+        }
+
+        // Invocations are logged as `Program <id> invoke [<depth>]`, and end with either
+        // `Program <id> success` or `Program <id> failed: <error>`.
+        let mut words = line.split_whitespace();
+        if words.next() != Some("Program") {
+            continue;
+        }
+        let program = match words.next().map(Pubkey::from_str) {
+            Some(Ok(program)) => program,
+            _ => continue,
+        };
+        match words.next() {
+            Some("invoke") => stack.push(program),
+            Some("success") | Some("failed:") => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bridge::events::EVENT_LOG_PREFIX;
+
+    fn invoke(program: &Pubkey, depth: u8) -> String {
+        format!("Program {} invoke [{}]", program, depth)
+    }
+
+    fn success(program: &Pubkey) -> String {
+        format!("Program {} success", program)
+    }
+
+    fn log(event: &Event) -> String {
+        format!("{}{}", PROGRAM_LOG, event.to_log())
+    }
+
+    #[test]
+    fn test_parse_events() {
+        let bridge = Pubkey::new_unique();
+        let token_bridge = Pubkey::new_unique();
+
+        let posted = Event::MessagePosted {
+            message: Pubkey::new_unique(),
+            emitter: token_bridge,
+            sequence: 7,
+            nonce: 1,
+            consistency_level: 32,
+        };
+        let fees = Event::FeesSet { fee: 100 };
+
+        let logs = vec![
+            // A message posted by the token bridge through a cross-program invocation.
+            invoke(&token_bridge, 1),
+            format!("{}Instruction: Transfer", PROGRAM_LOG),
+            invoke(&bridge, 2),
+            format!("{}Sequence: 7", PROGRAM_LOG),
+            log(&posted),
+            format!("Program {} consumed 20000 of 180000 compute units", bridge),
+            success(&bridge),
+            // Events logged by other programs are ignored.
+            log(&Event::Unpaused),
+            success(&token_bridge),
+            // A direct call to the bridge.
+            invoke(&bridge, 1),
+            log(&fees),
+            success(&bridge),
+        ];
+
+        assert_eq!(parse_events(&bridge, &logs), vec![posted, fees]);
+    }
+
+    #[test]
+    fn test_parse_events_failed() {
+        let bridge = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+
+        let logs = vec![
+            invoke(&bridge, 1),
+            format!("Program {} failed: custom program error: 0x1", bridge),
+            invoke(&other, 1),
+            log(&Event::Unpaused),
+            format!("{}{}not base64", PROGRAM_LOG, EVENT_LOG_PREFIX),
+            success(&other),
+        ];
+
+        assert!(parse_events(&bridge, &logs).is_empty());
+    }
+}
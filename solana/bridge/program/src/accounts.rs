@@ -1,5 +1,6 @@
 pub mod bridge;
 pub mod claim;
+pub mod denylist;
 pub mod fee_collector;
 pub mod guardian_set;
 pub mod posted_message;
@@ -10,6 +11,7 @@ pub mod signature_set;
 pub use self::{
     bridge::*,
     claim::*,
+    denylist::*,
     fee_collector::*,
     guardian_set::*,
     posted_message::*,
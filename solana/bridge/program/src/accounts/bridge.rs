@@ -5,6 +5,8 @@ use borsh::{
     BorshDeserialize,
     BorshSerialize,
 };
+use std::io::Write;
+
 use serde::{
     Deserialize,
     Serialize,
@@ -19,7 +21,11 @@ use solitaire::{
 
 pub type Bridge<'a, const State: AccountState> = Derive<Data<'a, BridgeData, { State }>, "Bridge">;
 
-#[derive(Clone, Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+/// Maximum number of emitters that may keep posting messages while the bridge is paused. The
+/// allowlist is stored inline, so the Bridge account keeps a fixed size.
+pub const MAX_PAUSE_ALLOWLIST: usize = 8;
+
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct BridgeData {
     /// The current guardian set index, used to decide which signature sets to accept.
     pub guardian_set_index: u32,
@@ -29,6 +35,80 @@ pub struct BridgeData {
 
     /// Bridge configuration, which is set once upon initialization.
     pub config: BridgeConfig,
+
+    /// Fraction of the guardian set that must sign a VAA for it to be posted.
+    pub quorum: Quorum,
+
+    /// Set by governance to halt `post_message` and `post_vaa`.
+    pub paused: bool,
+
+    /// Emitters that may keep posting while the bridge is paused, unused entries are zeroed.
+    pub pause_allowlist: [AllowedEmitter; MAX_PAUSE_ALLOWLIST],
+
+    /// Set when the account was created before the fields above were added and still ends after
+    /// `config`. It is written back in that layout until `migrate_bridge` resizes it, so the
+    /// fields above keep their defaults until then.
+    #[serde(skip)]
+    pub legacy_layout: bool,
+}
+
+/// Borsh is implemented by hand so that accounts in the legacy layout keep loading, with the
+/// quorum, pause state and allowlist at their defaults.
+impl BorshDeserialize for BridgeData {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let guardian_set_index = BorshDeserialize::deserialize(buf)?;
+        let last_lamports = BorshDeserialize::deserialize(buf)?;
+        let config = BorshDeserialize::deserialize(buf)?;
+
+        if buf.is_empty() {
+            return Ok(BridgeData {
+                guardian_set_index,
+                last_lamports,
+                config,
+                legacy_layout: true,
+                ..Default::default()
+            });
+        }
+
+        Ok(BridgeData {
+            guardian_set_index,
+            last_lamports,
+            config,
+            quorum: BorshDeserialize::deserialize(buf)?,
+            paused: BorshDeserialize::deserialize(buf)?,
+            pause_allowlist: BorshDeserialize::deserialize(buf)?,
+            legacy_layout: false,
+        })
+    }
+}
+
+impl BorshSerialize for BridgeData {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        BorshSerialize::serialize(&self.guardian_set_index, writer)?;
+        BorshSerialize::serialize(&self.last_lamports, writer)?;
+        BorshSerialize::serialize(&self.config, writer)?;
+
+        if self.legacy_layout {
+            return Ok(());
+        }
+
+        BorshSerialize::serialize(&self.quorum, writer)?;
+        BorshSerialize::serialize(&self.paused, writer)?;
+        BorshSerialize::serialize(&self.pause_allowlist, writer)
+    }
+}
+
+impl BridgeData {
+    /// Check whether an emitter may post messages, which is always the case unless the bridge is
+    /// paused.
+    pub fn is_emitter_allowed(&self, emitter_chain: u16, emitter_address: &[u8; 32]) -> bool {
+        !self.paused
+            || self.pause_allowlist.iter().any(|emitter| {
+                !emitter.is_empty()
+                    && emitter.chain == emitter_chain
+                    && emitter.address == *emitter_address
+            })
+    }
 }
 
 #[cfg(not(feature = "cpi"))]
@@ -57,3 +137,116 @@ pub struct BridgeConfig {
     /// Amount of lamports that needs to be paid to the protocol to post a message
     pub fee: u64,
 }
+
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct AllowedEmitter {
+    pub chain: u16,
+    pub address: [u8; 32],
+}
+
+impl AllowedEmitter {
+    /// Chain ID 0 is never assigned, marking unused allowlist entries.
+    pub fn is_empty(&self) -> bool {
+        self.chain == 0
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct Quorum {
+    pub numerator: u8,
+    pub denominator: u8,
+}
+
+/// Bridges start out requiring signatures from more than 2/3 of the guardian set.
+impl Default for Quorum {
+    fn default() -> Self {
+        Quorum {
+            numerator: 2,
+            denominator: 3,
+        }
+    }
+}
+
+impl Quorum {
+    /// A quorum must be a strict majority that a full guardian set can still reach, so at least
+    /// half and less than all of the set before rounding.
+    pub fn is_valid(&self) -> bool {
+        let numerator = self.numerator as u16;
+        let denominator = self.denominator as u16;
+        denominator > 0 && numerator < denominator && numerator * 2 >= denominator
+    }
+
+    /// Calculate how many signatures are required to reach consensus. This calculation is in
+    /// expanded form to ease auditing.
+    pub fn required_signatures(&self, guardians: usize) -> usize {
+        // Multiplication first so the division only rounds once.
+        let len = guardians * self.numerator as usize;
+        // Division rounds down, to the largest count not above the quorum fraction.
+        let len = len / self.denominator as usize;
+        // One more signature to exceed the fraction.
+        len + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_layout_round_trip() {
+        let legacy = BridgeData {
+            guardian_set_index: 3,
+            last_lamports: 1_000,
+            config: BridgeConfig {
+                guardian_set_expiration_time: 86400,
+                fee: 500,
+            },
+            legacy_layout: true,
+            ..Default::default()
+        };
+        let bytes = legacy.try_to_vec().unwrap();
+        assert_eq!(bytes.len(), 24);
+
+        let bridge = BridgeData::try_from_slice(&bytes).unwrap();
+        assert!(bridge.legacy_layout);
+        assert_eq!(bridge.guardian_set_index, 3);
+        assert_eq!(bridge.config.fee, 500);
+        assert!(bridge.quorum == Quorum::default());
+        assert!(!bridge.paused);
+        assert!(bridge.is_emitter_allowed(1, &[0; 32]));
+        assert_eq!(bridge.try_to_vec().unwrap(), bytes);
+
+        let migrated = BridgeData {
+            legacy_layout: false,
+            ..bridge
+        };
+        let bridge = BridgeData::try_from_slice(&migrated.try_to_vec().unwrap()).unwrap();
+        assert!(!bridge.legacy_layout);
+        assert_eq!(bridge.guardian_set_index, 3);
+    }
+
+    #[test]
+    fn test_default_quorum_parity() {
+        // The formula used before the quorum became configurable.
+        let legacy = |len: usize| ((len * 10) / 3) * 2 / 10 + 1;
+        for len in 0..=crate::MAX_LEN_GUARDIAN_KEYS {
+            assert_eq!(Quorum::default().required_signatures(len), legacy(len));
+        }
+    }
+
+    #[test]
+    fn test_quorum_is_valid() {
+        let quorum = |numerator, denominator| Quorum {
+            numerator,
+            denominator,
+        };
+        assert!(quorum(2, 3).is_valid());
+        assert!(quorum(1, 2).is_valid());
+        assert!(quorum(254, 255).is_valid());
+        assert!(!quorum(1, 3).is_valid());
+        assert!(!quorum(3, 3).is_valid());
+        assert!(!quorum(0, 0).is_valid());
+    }
+}
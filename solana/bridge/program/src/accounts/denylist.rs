@@ -0,0 +1,163 @@
+//! The Denylist account tracks signature sets and VAAs revoked by governance, which can no longer
+//! be used to post VAAs.
+//!
+//! Entries are either signature set addresses or VAA body hashes. Both are 32 bytes, and are
+//! checked against the same list. Governance adds entries with `revoke` and removes them again with
+//! `unrevoke`.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solana_program::pubkey::Pubkey;
+use solitaire::{
+    AccountOwner,
+    AccountState,
+    Data,
+    Derive,
+    Owned,
+};
+use std::{
+    io::{
+        Error,
+        ErrorKind::InvalidData,
+        Write,
+    },
+    str::FromStr,
+};
+
+pub type Denylist<'a, const State: AccountState> =
+    Derive<Data<'a, DenylistData, { State }>, "Denylist">;
+
+/// Maximum number of revoked entries. Accounts cannot grow once created, so the Denylist is always
+/// allocated at full size. Entries that are no longer needed can be unrevoked to make room.
+pub const MAX_DENYLIST_ENTRIES: usize = 64;
+
+/// Signature set accounts that were rejected before the Denylist existed. These seed the Denylist
+/// when it is created, and are still rejected by bridges that have not created it yet.
+pub const LEGACY_DENYLIST: &[&str; 16] = &[
+    "18eK1799CaNMGCUnnCt1Kq2uwKkax6T2WmtrDsZuVFQ",
+    "2g6NCUUPaD6AxdHPQMVLpjpAvBfKMek6dDiGUe2A6T33",
+    "3hYV5968hNzbqUfcvnQ6v9D5h32hEwGJn19c47N3unNj",
+    "76eEyhaEKs4mesjiQiu8bghvwDHNxJW3EfcpbNC78y1z",
+    "7PdcxSn7xk2UN5VYmKnJ2Q64PdBhbBQFf4RwHqhQCMgv",
+    "94wXN3z3Pph2vMVaviZSouo7oCDqt4fekvqT3FYJSrWA",
+    "AXe9VXd9jjXkBxSdvgj4bHSZNeqxY73sSQEsp1tnekY4",
+    "B2hS49B8n4Ad6cxZLoAjz7Hux7Kf17D5xUX3neDPHpug",
+    "BTXnYYjnfXByqJprarqzp65Yha2XwQVmg8V8KWBhr6aA",
+    "Bzb5G4Y8QcaMVMQq3r8q1SuKSxtgnWSFdKCEisJCbcBP",
+    "CJfRUQxyonG6B5mnztsNUqxknbFT89DJdrdrzV9F96mU",
+    "CK1j9TxWP1T5w1QzFu4vPDAbUR34mfVqvk5wziE8TzST",
+    "E8qKJMwzBCiHCHUmBEcL631kN5CjfsHNx24osFLfHg69",
+    "EtMw1nQ4AQaH53RjYz3pRk12rrqWjcYjPDETphYJzmCX",
+    "EVNwqfgkUnJoMqBqiHgDfa3TLZPQocX1hpcbAXbpcSLv",
+    "FixSiDfTxvoy5Zgjp5KdFU8U23ChwCxPWY3WTkmMW2fU",
+];
+
+#[derive(Clone, Default)]
+pub struct DenylistData {
+    /// Revoked signature set addresses and VAA body hashes.
+    pub entries: Vec<[u8; 32]>,
+}
+
+impl DenylistData {
+    /// A Denylist holding the legacy entries.
+    pub fn legacy() -> Self {
+        DenylistData {
+            entries: LEGACY_DENYLIST
+                .iter()
+                .map(|key| Pubkey::from_str(key).unwrap().to_bytes())
+                .collect(),
+        }
+    }
+
+    pub fn contains(&self, entry: &[u8; 32]) -> bool {
+        self.entries.contains(entry)
+    }
+}
+
+/// Check an entry against the legacy entries, for bridges without a Denylist.
+pub fn legacy_denylist_contains(entry: &[u8; 32]) -> bool {
+    LEGACY_DENYLIST.contains(&&*Pubkey::new_from_array(*entry).to_string())
+}
+
+impl BorshSerialize for DenylistData {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        if self.entries.len() > MAX_DENYLIST_ENTRIES {
+            return Err(Error::new(InvalidData, "Too many Denylist entries"));
+        }
+
+        (self.entries.len() as u32).serialize(writer)?;
+        for entry in self.entries.iter() {
+            writer.write_all(entry)?;
+        }
+
+        // Pad to full size so the account is allocated with room for every entry.
+        for _ in self.entries.len()..MAX_DENYLIST_ENTRIES {
+            writer.write_all(&[0u8; 32])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for DenylistData {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let len = u32::deserialize(buf)? as usize;
+        if len > MAX_DENYLIST_ENTRIES || buf.len() < MAX_DENYLIST_ENTRIES * 32 {
+            return Err(Error::new(InvalidData, "Invalid Denylist"));
+        }
+
+        let entries = buf[..len * 32]
+            .chunks_exact(32)
+            .map(|entry| {
+                let mut e = [0u8; 32];
+                e.copy_from_slice(entry);
+                e
+            })
+            .collect();
+
+        *buf = &buf[MAX_DENYLIST_ENTRIES * 32..];
+        Ok(DenylistData { entries })
+    }
+}
+
+#[cfg(not(feature = "cpi"))]
+impl Owned for DenylistData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+#[cfg(feature = "cpi")]
+impl Owned for DenylistData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::Other(Pubkey::from_str(env!("BRIDGE_ADDRESS")).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_denylist_roundtrip() {
+        let empty = DenylistData::default().try_to_vec().unwrap();
+        let mut denylist = DenylistData::legacy();
+        denylist.entries.push([7u8; 32]);
+        let full = denylist.try_to_vec().unwrap();
+
+        // The size is fixed, so accounts never need to grow.
+        assert_eq!(empty.len(), 4 + MAX_DENYLIST_ENTRIES * 32);
+        assert_eq!(full.len(), empty.len());
+
+        let decoded = DenylistData::try_from_slice(&full).unwrap();
+        assert_eq!(decoded.entries, denylist.entries);
+        assert!(decoded.contains(&[7u8; 32]));
+        assert!(legacy_denylist_contains(&decoded.entries[0]));
+        assert!(!legacy_denylist_contains(&[7u8; 32]));
+
+        denylist.entries = vec![[0u8; 32]; MAX_DENYLIST_ENTRIES + 1];
+        assert!(denylist.try_to_vec().is_err());
+    }
+}
@@ -3,6 +3,7 @@ use borsh::{
     BorshDeserialize,
     BorshSerialize,
 };
+use solana_program::pubkey::Pubkey;
 use solitaire::{
     processors::seeded::Seeded,
     AccountOwner,
@@ -87,6 +88,30 @@ impl Clone for PostedVAAData {
         }
     }
 }
+/// Records who paid for a `PostedVAA`, so that they alone can close it and receive its rent. It is
+/// kept apart from the `PostedVAA` so that account keeps the layout programs already read. VAAs
+/// posted before payers were recorded have none, and cannot be closed.
+pub type PostedVAAPayer<'b, const State: AccountState> = Data<'b, PostedVAAPayerData, { State }>;
+
+impl<'a, const State: AccountState> Seeded<&PostedVAADerivationData>
+    for PostedVAAPayer<'a, { State }>
+{
+    fn seeds(data: &PostedVAADerivationData) -> Vec<Vec<u8>> {
+        vec![b"PostedVAAPayer".to_vec(), data.payload_hash.to_vec()]
+    }
+}
+
+#[derive(Default, Clone, Copy, BorshSerialize, BorshDeserialize)]
+pub struct PostedVAAPayerData {
+    pub payer: Pubkey,
+}
+
+impl Owned for PostedVAAPayerData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
 #[cfg(not(feature = "cpi"))]
 impl Owned for PostedVAAData {
     fn owner(&self) -> AccountOwner {
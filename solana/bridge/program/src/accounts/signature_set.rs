@@ -4,16 +4,18 @@ use borsh::{
     BorshDeserialize,
     BorshSerialize,
 };
+use solana_program::pubkey::Pubkey;
 use solitaire::{
     AccountOwner,
     AccountState,
     Data,
     Owned,
 };
+use std::io::Write;
 
 pub type SignatureSet<'b, const State: AccountState> = Data<'b, SignatureSetData, { State }>;
 
-#[derive(Default, BorshSerialize, BorshDeserialize)]
+#[derive(Default)]
 pub struct SignatureSetData {
     /// Signatures of validators
     pub signatures: Vec<bool>,
@@ -23,6 +25,54 @@ pub struct SignatureSetData {
 
     /// Index of the guardian set
     pub guardian_set_index: u32,
+
+    /// Account that paid for this one, refunded when it is closed. Signature sets created before
+    /// this was recorded hold the default key and cannot be closed.
+    pub payer: Pubkey,
+
+    /// Set when the account was created before `payer` was recorded and ends before it. It is
+    /// written back in that layout, as the account has no room for the payer.
+    pub legacy_layout: bool,
+}
+
+impl BorshSerialize for SignatureSetData {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        BorshSerialize::serialize(&self.signatures, writer)?;
+        BorshSerialize::serialize(&self.hash, writer)?;
+        BorshSerialize::serialize(&self.guardian_set_index, writer)?;
+
+        if self.legacy_layout {
+            return Ok(());
+        }
+
+        BorshSerialize::serialize(&self.payer, writer)
+    }
+}
+
+impl BorshDeserialize for SignatureSetData {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let signatures = BorshDeserialize::deserialize(buf)?;
+        let hash = BorshDeserialize::deserialize(buf)?;
+        let guardian_set_index = BorshDeserialize::deserialize(buf)?;
+
+        if buf.is_empty() {
+            return Ok(SignatureSetData {
+                signatures,
+                hash,
+                guardian_set_index,
+                payer: Pubkey::default(),
+                legacy_layout: true,
+            });
+        }
+
+        Ok(SignatureSetData {
+            signatures,
+            hash,
+            guardian_set_index,
+            payer: BorshDeserialize::deserialize(buf)?,
+            legacy_layout: false,
+        })
+    }
 }
 
 impl Owned for SignatureSetData {
@@ -30,3 +80,42 @@ impl Owned for SignatureSetData {
         AccountOwner::This
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_layout_round_trip() {
+        let legacy = SignatureSetData {
+            signatures: vec![true, false, true],
+            hash: [7; 32],
+            guardian_set_index: 2,
+            legacy_layout: true,
+            ..Default::default()
+        };
+        let bytes = legacy.try_to_vec().unwrap();
+        assert_eq!(bytes.len(), 4 + 3 + 32 + 4);
+
+        // Signatures keep being recorded into sets created before the payer was.
+        let mut set = SignatureSetData::try_from_slice(&bytes).unwrap();
+        assert!(set.legacy_layout);
+        assert_eq!(set.payer, Pubkey::default());
+        set.signatures[1] = true;
+        let bytes = set.try_to_vec().unwrap();
+        assert_eq!(bytes.len(), 4 + 3 + 32 + 4);
+        assert_eq!(
+            SignatureSetData::try_from_slice(&bytes).unwrap().signatures,
+            vec![true; 3]
+        );
+
+        let current = SignatureSetData {
+            payer: Pubkey::new_unique(),
+            legacy_layout: false,
+            ..set
+        };
+        let set = SignatureSetData::try_from_slice(&current.try_to_vec().unwrap()).unwrap();
+        assert!(!set.legacy_layout);
+        assert_eq!(set.payer, current.payer);
+    }
+}
@@ -0,0 +1,264 @@
+//! Signature sets and posted VAAs are only needed until a VAA has been posted and consumed, these
+//! instructions close them and refund their rent to whoever paid for them. Replay protection is
+//! unaffected as it relies on `Claim` accounts, which are never closed.
+//!
+//! Signature sets verified for a batch are closed with a proof that one of the batch's VAAs has been
+//! posted, and only by their payer, as the other VAAs of the batch may still need them.
+//!
+//! A closed `PostedVAA` can only be posted again while the guardian set that signed it is active,
+//! after which anything it carried that was never redeemed is lost. Only its payer, recorded in a
+//! `PostedVAAPayer` account that is closed along with it, can close it, so that decision stays with
+//! whoever posted it.
+
+use solana_program::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::clock::Clock,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    *,
+};
+
+use crate::{
+    accounts::{
+        MessageData,
+        PostedVAA,
+        PostedVAAData,
+        PostedVAADerivationData,
+        PostedVAAPayer,
+        PostedVAAPayerData,
+        SignatureSetData,
+    },
+    api::post_vaa::body_hash,
+    batch,
+    error::Error::{
+        InvalidRefundAccount,
+        MathOverflow,
+        PostedVAATooRecent,
+    },
+    PostVAAData,
+};
+
+/// Seconds a `PostedVAA` must exist before it can be closed, giving consumers time to act on it.
+/// Can be overridden when building with the `POSTED_VAA_MIN_AGE` environment variable.
+const POSTED_VAA_MIN_AGE: i64 = match option_env!("POSTED_VAA_MIN_AGE") {
+    Some(age) => parse_seconds(age),
+    None => 30 * 24 * 60 * 60,
+};
+
+/// Parses a decimal number of seconds. This runs at compile time, so a malformed
+/// `POSTED_VAA_MIN_AGE` fails the build rather than the program.
+const fn parse_seconds(s: &str) -> i64 {
+    let bytes = s.as_bytes();
+    assert!(
+        !bytes.is_empty(),
+        "POSTED_VAA_MIN_AGE must be a number of seconds"
+    );
+
+    let mut seconds: i64 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        assert!(
+            bytes[i].is_ascii_digit(),
+            "POSTED_VAA_MIN_AGE must be a number of seconds"
+        );
+        seconds = match seconds.checked_mul(10) {
+            Some(seconds) => seconds,
+            None => panic!("POSTED_VAA_MIN_AGE is too large"),
+        };
+        seconds = match seconds.checked_add((bytes[i] - b'0') as i64) {
+            Some(seconds) => seconds,
+            None => panic!("POSTED_VAA_MIN_AGE is too large"),
+        };
+        i += 1;
+    }
+    seconds
+}
+
+#[derive(FromAccounts)]
+pub struct CloseSignatureSet<'b> {
+    /// Signature set to close, read by hand as it must not be written back once closed.
+    pub signature_set: Mut<Info<'b>>,
+
+    /// The VAA the signatures were verified for, which must already be posted.
+    pub posted_vaa: PostedVAA<'b, { AccountState::Initialized }>,
+
+    /// Account that paid for the signature set, receiving its lamports.
+    pub payer: Mut<Info<'b>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct CloseSignatureSetData {}
+
+pub fn close_signature_set(
+    ctx: &ExecutionContext,
+    accs: &mut CloseSignatureSet,
+    _data: CloseSignatureSetData,
+) -> Result<()> {
+    if accs.signature_set.owner != ctx.program_id {
+        return Err(SolitaireError::InvalidOwner(*accs.signature_set.owner));
+    }
+
+    let signature_set = SignatureSetData::try_from_slice(&accs.signature_set.data.borrow())?;
+    check_refund_account(&signature_set.payer, &accs.payer)?;
+
+    // Once a VAA is posted its signature set is never read again.
+    accs.posted_vaa.verify_derivation(
+        ctx.program_id,
+        &PostedVAADerivationData {
+            payload_hash: signature_set.hash.to_vec(),
+        },
+    )?;
+
+    close_account(&accs.signature_set, &accs.payer)
+}
+
+#[derive(FromAccounts)]
+pub struct CloseBatchedSignatureSet<'b> {
+    /// Signature set to close, read by hand as it must not be written back once closed.
+    pub signature_set: Mut<Info<'b>>,
+
+    /// Any VAA of the batch the signatures were verified for, which must already be posted.
+    pub posted_vaa: PostedVAA<'b, { AccountState::Initialized }>,
+
+    /// Account that paid for the signature set, which must sign and receives its lamports.
+    pub payer: Mut<Signer<Info<'b>>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct CloseBatchedSignatureSetData {
+    /// Proof that the posted VAA is included in the batch the signature set was verified for.
+    pub proof: Vec<[u8; 32]>,
+}
+
+pub fn close_batched_signature_set(
+    ctx: &ExecutionContext,
+    accs: &mut CloseBatchedSignatureSet,
+    data: CloseBatchedSignatureSetData,
+) -> Result<()> {
+    if accs.signature_set.owner != ctx.program_id {
+        return Err(SolitaireError::InvalidOwner(*accs.signature_set.owner));
+    }
+
+    let signature_set = SignatureSetData::try_from_slice(&accs.signature_set.data.borrow())?;
+    check_refund_account(&signature_set.payer, &accs.payer)?;
+
+    // The posted VAA must be at the address derived from its own body, and included in the batch.
+    let hash = posted_vaa_hash(&accs.posted_vaa.message)?;
+    accs.posted_vaa.verify_derivation(
+        ctx.program_id,
+        &PostedVAADerivationData {
+            payload_hash: hash.to_vec(),
+        },
+    )?;
+    if signature_set.hash != batch::digest(&batch::root_from_proof(&hash, &data.proof)) {
+        return Err(ProgramError::InvalidAccountData.into());
+    }
+
+    close_account(&accs.signature_set, &accs.payer)
+}
+
+#[derive(FromAccounts)]
+pub struct ClosePostedVAA<'b> {
+    /// Posted VAA to close, read by hand as it must not be written back once closed.
+    pub posted_vaa: Mut<Info<'b>>,
+
+    /// Record of who paid for the posted VAA, closed along with it so the VAA can be posted again.
+    pub posted_vaa_payer: Mut<Info<'b>>,
+
+    /// Account that paid for the posted VAA, which must sign and receives its lamports.
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Clock used to check the age of the posted VAA.
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct ClosePostedVAAData {}
+
+pub fn close_posted_vaa(
+    ctx: &ExecutionContext,
+    accs: &mut ClosePostedVAA,
+    _data: ClosePostedVAAData,
+) -> Result<()> {
+    if accs.posted_vaa.owner != ctx.program_id {
+        return Err(SolitaireError::InvalidOwner(*accs.posted_vaa.owner));
+    }
+
+    let posted_vaa = PostedVAAData::try_from_slice(&accs.posted_vaa.data.borrow())?;
+
+    // Only accounts at the address derived from their own body are posted VAAs, this rules out
+    // messages which share the same layout.
+    let message = &posted_vaa.message;
+    let derivation_data = PostedVAADerivationData {
+        payload_hash: posted_vaa_hash(message)?.to_vec(),
+    };
+    let derived =
+        PostedVAA::<'_, { AccountState::Initialized }>::key(&derivation_data, ctx.program_id);
+    if derived != *accs.posted_vaa.key {
+        return Err(SolitaireError::InvalidDerive(*accs.posted_vaa.key, derived));
+    }
+
+    let derived =
+        PostedVAAPayer::<'_, { AccountState::Initialized }>::key(&derivation_data, ctx.program_id);
+    if derived != *accs.posted_vaa_payer.key {
+        return Err(SolitaireError::InvalidDerive(
+            *accs.posted_vaa_payer.key,
+            derived,
+        ));
+    }
+
+    // VAAs posted before payers were recorded have no record, and cannot be closed.
+    if accs.posted_vaa_payer.owner != ctx.program_id {
+        return Err(InvalidRefundAccount.into());
+    }
+    let record = PostedVAAPayerData::try_from_slice(&accs.posted_vaa_payer.data.borrow())?;
+    check_refund_account(&record.payer, &accs.payer)?;
+
+    let age = accs.clock.unix_timestamp - message.submission_time as i64;
+    if age < POSTED_VAA_MIN_AGE {
+        return Err(PostedVAATooRecent.into());
+    }
+
+    close_account(&accs.posted_vaa, &accs.payer)?;
+    close_account(&accs.posted_vaa_payer, &accs.payer)
+}
+
+/// Hash the body of a posted VAA, which is the seed of its address.
+fn posted_vaa_hash(message: &MessageData) -> Result<[u8; 32]> {
+    body_hash(&PostVAAData {
+        version: message.vaa_version,
+        guardian_set_index: 0,
+        timestamp: message.vaa_time,
+        nonce: message.nonce,
+        emitter_chain: message.emitter_chain,
+        emitter_address: message.emitter_address,
+        sequence: message.sequence,
+        consistency_level: message.consistency_level,
+        payload: message.payload.clone(),
+    })
+}
+
+/// Refunds may only go to the recorded payer. Accounts created before the payer was recorded hold
+/// the default key, and can never be closed.
+fn check_refund_account(payer: &Pubkey, refund: &AccountInfo) -> Result<()> {
+    if *payer == Pubkey::default() || payer != refund.key {
+        return Err(InvalidRefundAccount.into());
+    }
+    Ok(())
+}
+
+/// Move all lamports out of `account` so the runtime removes it at the end of the transaction,
+/// zeroing its data so nothing later in the same transaction can read it as initialized.
+fn close_account(account: &AccountInfo, recipient: &AccountInfo) -> Result<()> {
+    let lamports = recipient
+        .lamports()
+        .checked_add(account.lamports())
+        .ok_or(MathOverflow)?;
+    **recipient.lamports.borrow_mut() = lamports;
+    **account.lamports.borrow_mut() = 0;
+    account.data.borrow_mut().fill(0);
+    Ok(())
+}
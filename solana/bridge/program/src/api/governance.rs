@@ -18,20 +18,41 @@ use crate::{
             self,
             Claim,
         },
+        AllowedEmitter,
         Bridge,
+        BridgeData,
+        Denylist,
         GuardianSet,
         GuardianSetDerivationData,
+        Quorum,
+        MAX_DENYLIST_ENTRIES,
+        MAX_PAUSE_ALLOWLIST,
     },
+    api::ForeignAddress,
     error::Error::{
+        BridgeNotMigrated,
+        BridgePaused,
+        DenylistFull,
         InvalidFeeRecipient,
         InvalidGovernanceKey,
         InvalidGovernanceWithdrawal,
         InvalidGuardianSetUpgrade,
+        InvalidQuorum,
+        PauseAllowlistTooLong,
+    },
+    events::{
+        self,
+        Event,
     },
     types::{
         GovernancePayloadGuardianSetChange,
+        GovernancePayloadPause,
+        GovernancePayloadRevoke,
         GovernancePayloadSetMessageFee,
+        GovernancePayloadSetQuorum,
         GovernancePayloadTransferFees,
+        GovernancePayloadUnpause,
+        GovernancePayloadUnrevoke,
         GovernancePayloadUpgrade,
     },
     DeserializePayload,
@@ -39,20 +60,49 @@ use crate::{
     CHAIN_ID_SOLANA,
 };
 
+/// Check whether an emitter is the known governance key on Solana.
+fn is_governance_emitter(emitter_chain: u16, emitter_address: &ForeignAddress) -> bool {
+    let expected_emitter = std::env!("EMITTER_ADDRESS");
+    let current_emitter = format!("{}", Pubkey::new_from_array(*emitter_address));
+    expected_emitter == current_emitter && emitter_chain == CHAIN_ID_SOLANA
+}
+
 /// Fail if the emitter is not the known governance key, or the emitting chain is not Solana.
 fn verify_governance<T>(vaa: &PayloadMessage<T>) -> Result<()>
 where
     T: DeserializePayload,
 {
-    let expected_emitter = std::env!("EMITTER_ADDRESS");
-    let current_emitter = format!("{}", Pubkey::new_from_array(vaa.meta().emitter_address));
-    if expected_emitter != current_emitter || vaa.meta().emitter_chain != CHAIN_ID_SOLANA {
+    if !is_governance_emitter(vaa.meta().emitter_chain, &vaa.meta().emitter_address) {
         Err(InvalidGovernanceKey.into())
     } else {
         Ok(())
     }
 }
 
+/// Fail if the bridge is paused and the emitter is not allowed to post. Governance can always post
+/// so that the bridge can be unpaused.
+pub(crate) fn check_paused(
+    bridge: &BridgeData,
+    emitter_chain: u16,
+    emitter_address: &ForeignAddress,
+) -> Result<()> {
+    if !bridge.is_emitter_allowed(emitter_chain, emitter_address)
+        && !is_governance_emitter(emitter_chain, emitter_address)
+    {
+        return Err(BridgePaused.into());
+    }
+    Ok(())
+}
+
+/// Fail if the Bridge account is still in the legacy layout, which has no room for the quorum or
+/// pause state. `migrate_bridge` must be called first.
+fn check_migrated(bridge: &BridgeData) -> Result<()> {
+    if bridge.legacy_layout {
+        return Err(BridgeNotMigrated.into());
+    }
+    Ok(())
+}
+
 #[derive(FromAccounts)]
 pub struct UpgradeContract<'b> {
     /// Payer for account creation (vaa-claim)
@@ -114,6 +164,10 @@ pub fn upgrade_contract(
     let seeds = seeds.as_slice();
     invoke_signed(&upgrade_ix, ctx.accounts, &[seeds])?;
 
+    events::emit(Event::ContractUpgraded {
+        new_contract: accs.vaa.new_contract,
+    });
+
     Ok(())
 }
 
@@ -195,6 +249,11 @@ pub fn upgrade_guardian_set(
     // Set guardian set index
     accs.bridge.guardian_set_index = accs.vaa.new_guardian_set_index;
 
+    events::emit(Event::GuardianSetUpgraded {
+        old_index: accs.guardian_set_old.index,
+        new_index: accs.guardian_set_new.index,
+    });
+
     Ok(())
 }
 
@@ -220,6 +279,11 @@ pub fn set_fees(ctx: &ExecutionContext, accs: &mut SetFees, _data: SetFeesData)
     verify_governance(&accs.vaa)?;
     claim::consume(ctx, &accs.payer.key, &mut accs.claim, &accs.vaa)?;
     accs.bridge.config.fee = accs.vaa.fee.as_u64();
+
+    events::emit(Event::FeesSet {
+        fee: accs.bridge.config.fee,
+    });
+
     Ok(())
 }
 
@@ -285,5 +349,198 @@ pub fn transfer_fees(
     let seeds = seeds.as_slice();
     invoke_signed(&transfer_ix, ctx.accounts, &[seeds])?;
 
+    events::emit(Event::FeesTransferred {
+        recipient: *accs.recipient.key,
+        amount: accs.vaa.amount.as_u64(),
+    });
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct SetQuorum<'b> {
+    /// Payer for account creation (vaa-claim)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Bridge config
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetQuorum>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetQuorumData {}
+
+pub fn set_quorum(
+    ctx: &ExecutionContext,
+    accs: &mut SetQuorum,
+    _data: SetQuorumData,
+) -> Result<()> {
+    verify_governance(&accs.vaa)?;
+    check_migrated(&accs.bridge)?;
+    claim::consume(ctx, &accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    let quorum = Quorum {
+        numerator: accs.vaa.numerator,
+        denominator: accs.vaa.denominator,
+    };
+    if !quorum.is_valid() {
+        return Err(InvalidQuorum.into());
+    }
+
+    accs.bridge.quorum = quorum;
+
+    events::emit(Event::QuorumSet {
+        numerator: quorum.numerator,
+        denominator: quorum.denominator,
+    });
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct Pause<'b> {
+    /// Payer for account creation (vaa-claim)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Bridge config
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadPause>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct PauseData {}
+
+pub fn pause(ctx: &ExecutionContext, accs: &mut Pause, _data: PauseData) -> Result<()> {
+    verify_governance(&accs.vaa)?;
+    check_migrated(&accs.bridge)?;
+    claim::consume(ctx, &accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    if accs.vaa.allowlist.len() > MAX_PAUSE_ALLOWLIST {
+        return Err(PauseAllowlistTooLong.into());
+    }
+
+    // Pausing again replaces the allowlist rather than extending it.
+    let mut allowlist = [AllowedEmitter::default(); MAX_PAUSE_ALLOWLIST];
+    allowlist[..accs.vaa.allowlist.len()].copy_from_slice(&accs.vaa.allowlist);
+
+    accs.bridge.paused = true;
+    accs.bridge.pause_allowlist = allowlist;
+
+    events::emit(Event::Paused {
+        allowlist: accs.vaa.allowlist.clone(),
+    });
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct Unpause<'b> {
+    /// Payer for account creation (vaa-claim)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Bridge config
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadUnpause>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct UnpauseData {}
+
+pub fn unpause(ctx: &ExecutionContext, accs: &mut Unpause, _data: UnpauseData) -> Result<()> {
+    verify_governance(&accs.vaa)?;
+    claim::consume(ctx, &accs.payer.key, &mut accs.claim, &accs.vaa)?;
+    accs.bridge.paused = false;
+    accs.bridge.pause_allowlist = Default::default();
+
+    events::emit(Event::Unpaused);
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct Revoke<'b> {
+    /// Payer for account creation (vaa-claim)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadRevoke>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Denylist to add the revoked entry to.
+    pub denylist: Mut<Denylist<'b, { AccountState::Initialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct RevokeData {}
+
+pub fn revoke(ctx: &ExecutionContext, accs: &mut Revoke, _data: RevokeData) -> Result<()> {
+    verify_governance(&accs.vaa)?;
+    claim::consume(ctx, &accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    if accs.denylist.contains(&accs.vaa.entry) {
+        return Ok(());
+    }
+
+    if accs.denylist.entries.len() >= MAX_DENYLIST_ENTRIES {
+        return Err(DenylistFull.into());
+    }
+
+    accs.denylist.entries.push(accs.vaa.entry);
+
+    events::emit(Event::Revoked {
+        entry: accs.vaa.entry,
+    });
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct Unrevoke<'b> {
+    /// Payer for account creation (vaa-claim)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadUnrevoke>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Denylist to remove the entry from.
+    pub denylist: Mut<Denylist<'b, { AccountState::Initialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct UnrevokeData {}
+
+pub fn unrevoke(ctx: &ExecutionContext, accs: &mut Unrevoke, _data: UnrevokeData) -> Result<()> {
+    verify_governance(&accs.vaa)?;
+    claim::consume(ctx, &accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    let entry = accs.vaa.entry;
+    if !accs.denylist.contains(&entry) {
+        return Ok(());
+    }
+
+    accs.denylist.entries.retain(|revoked| *revoked != entry);
+
+    events::emit(Event::Unrevoked { entry });
+
     Ok(())
 }
@@ -2,6 +2,8 @@ use crate::{
     accounts::{
         Bridge,
         BridgeConfig,
+        Denylist,
+        DenylistData,
         FeeCollector,
         GuardianSet,
         GuardianSetDerivationData,
@@ -9,7 +11,12 @@ use crate::{
     error::Error::TooManyGuardians,
     MAX_LEN_GUARDIAN_KEYS,
 };
-use solana_program::sysvar::clock::Clock;
+use solana_program::{
+    program::invoke,
+    rent::Rent,
+    system_instruction,
+    sysvar::clock::Clock,
+};
 use solitaire::{
     CreationLamports::Exempt,
     *,
@@ -93,3 +100,79 @@ pub fn initialize(
 
     Ok(())
 }
+
+#[derive(FromAccounts)]
+pub struct InitializeDenylist<'b> {
+    /// Location the Denylist will be allocated at.
+    pub denylist: Mut<Denylist<'b, { AccountState::Uninitialized }>>,
+
+    /// Payer for account creation.
+    pub payer: Mut<Payer<'b>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct InitializeDenylistData {}
+
+/// Create the Denylist, seeded with the signature sets that were rejected before it existed. This
+/// can be called by anyone, but only once.
+pub fn initialize_denylist(
+    ctx: &ExecutionContext,
+    accs: &mut InitializeDenylist,
+    _data: InitializeDenylistData,
+) -> Result<()> {
+    accs.denylist.entries = DenylistData::legacy().entries;
+    accs.denylist.create(ctx, accs.payer.key, Exempt)?;
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct MigrateBridge<'b> {
+    /// Bridge config, possibly still in the legacy layout.
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// Payer for the additional rent.
+    pub payer: Mut<Payer<'b>>,
+
+    /// Rent calculator for the resized account.
+    pub rent: Sysvar<'b, Rent>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct MigrateBridgeData {}
+
+/// Resize a Bridge account created before the quorum and pause state were added, so governance can
+/// set them. This can be called by anyone and does nothing once the account has been migrated.
+///
+/// Resizing needs the runtime's account data reallocation (the `do_support_realloc` feature), and
+/// fails on clusters where it is not active. Until then the account keeps working in the legacy
+/// layout, with the default quorum and no pause.
+pub fn migrate_bridge(
+    ctx: &ExecutionContext,
+    accs: &mut MigrateBridge,
+    _data: MigrateBridgeData,
+) -> Result<()> {
+    if !accs.bridge.legacy_layout {
+        return Ok(());
+    }
+
+    // The account is written back in the full layout once the flag is cleared.
+    accs.bridge.legacy_layout = false;
+    let size = accs.bridge.try_to_vec()?.len();
+
+    let required = accs.rent.minimum_balance(size);
+    let lamports = accs.bridge.info().lamports();
+    if required > lamports {
+        invoke(
+            &system_instruction::transfer(
+                accs.payer.key,
+                accs.bridge.info().key,
+                required - lamports,
+            ),
+            ctx.accounts,
+        )?;
+    }
+
+    accs.bridge.info().realloc(size, false)?;
+
+    Ok(())
+}
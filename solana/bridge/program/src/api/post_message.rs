@@ -7,12 +7,17 @@ use crate::{
         Sequence,
         SequenceDerivationData,
     },
+    api::governance::check_paused,
     error::Error::{
         EmitterChanged,
         InsufficientFees,
         InvalidPayloadLength,
         MathOverflow,
     },
+    events::{
+        self,
+        Event,
+    },
     types::ConsistencyLevel,
     IsSigned::*,
     MessageData,
@@ -174,7 +179,6 @@ pub fn post_message_unreliable(
     Ok(())
 }
 
-#[allow(unused_variables)] // message_key is used in `trace!`
 fn post_message_internal<'b>(
     ctx: &ExecutionContext,
     bridge: &mut Mut<Bridge<'b, { AccountState::Initialized }>>,
@@ -191,6 +195,8 @@ fn post_message_internal<'b>(
     trace!("Emitter Address: {}", emitter.info().key);
     trace!("Nonce: {}", data.nonce);
 
+    check_paused(bridge, CHAIN_ID_SOLANA, &emitter.key.to_bytes())?;
+
     let sequence_derivation = SequenceDerivationData {
         emitter_key: emitter.key,
     };
@@ -237,6 +243,14 @@ fn post_message_internal<'b>(
         ConsistencyLevel::Finalized => 32,
     };
 
+    events::emit(Event::MessagePosted {
+        message: *message_key,
+        emitter: *emitter.key,
+        sequence: message.sequence,
+        nonce: message.nonce,
+        consistency_level: message.consistency_level,
+    });
+
     // Bump sequence number
     trace!("New Sequence: {}", sequence.sequence + 1);
     sequence.sequence += 1;
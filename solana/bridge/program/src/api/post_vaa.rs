@@ -11,19 +11,28 @@ use solana_program::{
 
 use crate::{
     accounts::{
+        legacy_denylist_contains,
         Bridge,
+        Denylist,
         GuardianSet,
         GuardianSetDerivationData,
         PostedVAA,
         PostedVAADerivationData,
+        PostedVAAPayer,
         SignatureSet,
     },
+    api::governance::check_paused,
+    batch,
     error::Error::{
         GuardianSetMismatch,
         PostVAAConsensusFailed,
         PostVAAGuardianSetExpired,
         VAAInvalid,
     },
+    events::{
+        self,
+        Event,
+    },
 };
 use byteorder::{
     BigEndian,
@@ -71,6 +80,16 @@ pub struct PostVAA<'b> {
 
     /// Clock used for timestamping.
     pub clock: Sysvar<'b, Clock>,
+
+    /// Signature sets and VAAs revoked by governance. This account is required, even before the
+    /// Denylist has been created, so that callers cannot skip revocations by leaving it out.
+    ///
+    /// Instructions built before it was added pass the rent sysvar in its place and fail with
+    /// `InvalidDerive`, they must be rebuilt with `instructions::post_vaa`.
+    pub denylist: Denylist<'b, { AccountState::MaybeInitialized }>,
+
+    /// Records the payer, who alone can close the posted VAA and receive its rent.
+    pub posted_vaa_payer: Mut<PostedVAAPayer<'b, { AccountState::MaybeInitialized }>>,
 }
 
 #[derive(Default, BorshSerialize, BorshDeserialize)]
@@ -100,12 +119,46 @@ pub struct PostVAAData {
 }
 
 pub fn post_vaa(ctx: &ExecutionContext, accs: &mut PostVAA, vaa: PostVAAData) -> Result<()> {
+    // The signature set is expected to hold the VAA body hash, which is checked before posting.
+    let hash = accs.signature_set.hash;
+    post_vaa_internal(ctx, accs, vaa, hash, None)
+}
+
+#[derive(Default, BorshSerialize, BorshDeserialize, Clone)]
+pub struct PostBatchedVAAData {
+    pub vaa: PostVAAData,
+
+    /// Proof that the VAA is included in the batch the signature set was verified for.
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// Post a VAA whose signatures were verified for a whole batch of VAAs, see the `batch` module.
+pub fn post_batched_vaa(
+    ctx: &ExecutionContext,
+    accs: &mut PostVAA,
+    data: PostBatchedVAAData,
+) -> Result<()> {
+    let hash = body_hash(&data.vaa)?;
+    post_vaa_internal(ctx, accs, data.vaa, hash, Some(&data.proof))
+}
+
+/// Post a VAA with the given body hash. Without a proof the signature set must be over the VAA
+/// itself, otherwise over the batch the proof leads to.
+fn post_vaa_internal(
+    ctx: &ExecutionContext,
+    accs: &mut PostVAA,
+    vaa: PostVAAData,
+    hash: [u8; 32],
+    proof: Option<&[[u8; 32]]>,
+) -> Result<()> {
     let msg_derivation = PostedVAADerivationData {
-        payload_hash: accs.signature_set.hash.to_vec(),
+        payload_hash: hash.to_vec(),
     };
 
     accs.message
         .verify_derivation(ctx.program_id, &msg_derivation)?;
+    accs.posted_vaa_payer
+        .verify_derivation(ctx.program_id, &msg_derivation)?;
     accs.guardian_set
         .verify_derivation(ctx.program_id, &(&vaa).into())?;
 
@@ -114,24 +167,27 @@ pub fn post_vaa(ctx: &ExecutionContext, accs: &mut PostVAA, vaa: PostVAAData) ->
     }
 
     // Verify any required invariants before we process the instruction.
+    check_paused(&accs.bridge_info, vaa.emitter_chain, &vaa.emitter_address)?;
     check_active(&accs.guardian_set, &accs.clock)?;
-    check_valid_sigs(&accs.guardian_set, &accs.signature_set)?;
-    check_integrity(&vaa, &accs.signature_set)?;
+    check_valid_sigs(
+        &accs.guardian_set,
+        &accs.signature_set,
+        &accs.denylist,
+        &hash,
+    )?;
+    match proof {
+        None => check_integrity(&vaa, &accs.signature_set)?,
+        Some(proof) => check_batch_integrity(&hash, proof, &accs.signature_set)?,
+    }
 
     // Count the number of signatures currently present.
     let signature_count: usize = accs.signature_set.signatures.iter().filter(|v| **v).count();
 
-    // Calculate how many signatures are required to reach consensus. This calculation is in
-    // expanded form to ease auditing.
-    let required_consensus_count = {
-        let len = accs.guardian_set.keys.len();
-        // Fixed point number transformation with one decimal to deal with rounding.
-        let len = (len * 10) / 3;
-        // Multiplication by two to get a 2/3 quorum.
-        let len = len * 2;
-        // Division to bring number back into range.
-        len / 10 + 1
-    };
+    // Calculate how many signatures are required to reach consensus.
+    let required_consensus_count = accs
+        .bridge_info
+        .quorum
+        .required_signatures(accs.guardian_set.keys.len());
 
     if signature_count < required_consensus_count {
         return Err(PostVAAConsensusFailed.into());
@@ -147,9 +203,23 @@ pub fn post_vaa(ctx: &ExecutionContext, accs: &mut PostVAA, vaa: PostVAAData) ->
     accs.message.vaa_version = vaa.version;
     accs.message.vaa_time = vaa.timestamp;
     accs.message.vaa_signature_account = *accs.signature_set.info().key;
+    accs.message.submission_time = accs.clock.unix_timestamp as u32;
     accs.message
         .create(&msg_derivation, ctx, accs.payer.key, Exempt)?;
 
+    accs.posted_vaa_payer.payer = *accs.payer.key;
+    accs.posted_vaa_payer
+        .create(&msg_derivation, ctx, accs.payer.key, Exempt)?;
+
+    events::emit(Event::VAAPosted {
+        posted_vaa: *accs.message.info().key,
+        guardian_set_index: vaa.guardian_set_index,
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+        sequence: vaa.sequence,
+        hash,
+    });
+
     Ok(())
 }
 
@@ -172,38 +242,27 @@ fn check_active<'r>(
     Ok(())
 }
 
-// Static list of invalid signature accounts that are not allowed to post VAAs.
-static INVALID_SIGNATURES: &[&str; 16] = &[
-    "18eK1799CaNMGCUnnCt1Kq2uwKkax6T2WmtrDsZuVFQ",
-    "2g6NCUUPaD6AxdHPQMVLpjpAvBfKMek6dDiGUe2A6T33",
-    "3hYV5968hNzbqUfcvnQ6v9D5h32hEwGJn19c47N3unNj",
-    "76eEyhaEKs4mesjiQiu8bghvwDHNxJW3EfcpbNC78y1z",
-    "7PdcxSn7xk2UN5VYmKnJ2Q64PdBhbBQFf4RwHqhQCMgv",
-    "94wXN3z3Pph2vMVaviZSouo7oCDqt4fekvqT3FYJSrWA",
-    "AXe9VXd9jjXkBxSdvgj4bHSZNeqxY73sSQEsp1tnekY4",
-    "B2hS49B8n4Ad6cxZLoAjz7Hux7Kf17D5xUX3neDPHpug",
-    "BTXnYYjnfXByqJprarqzp65Yha2XwQVmg8V8KWBhr6aA",
-    "Bzb5G4Y8QcaMVMQq3r8q1SuKSxtgnWSFdKCEisJCbcBP",
-    "CJfRUQxyonG6B5mnztsNUqxknbFT89DJdrdrzV9F96mU",
-    "CK1j9TxWP1T5w1QzFu4vPDAbUR34mfVqvk5wziE8TzST",
-    "E8qKJMwzBCiHCHUmBEcL631kN5CjfsHNx24osFLfHg69",
-    "EtMw1nQ4AQaH53RjYz3pRk12rrqWjcYjPDETphYJzmCX",
-    "EVNwqfgkUnJoMqBqiHgDfa3TLZPQocX1hpcbAXbpcSLv",
-    "FixSiDfTxvoy5Zgjp5KdFU8U23ChwCxPWY3WTkmMW2fU",
-];
-
 /// The signatures in this instruction must be from the right guardian set.
 #[inline(always)]
 fn check_valid_sigs<'r>(
     guardian_set: &GuardianSet<'r, { AccountState::Initialized }>,
     signatures: &SignatureSet<'r, { AccountState::Initialized }>,
+    denylist: &Denylist<'r, { AccountState::MaybeInitialized }>,
+    hash: &[u8; 32],
 ) -> Result<()> {
     if signatures.guardian_set_index != guardian_set.index {
         return Err(GuardianSetMismatch.into());
     }
 
-    // Reject blacklisted signature accounts.
-    if INVALID_SIGNATURES.contains(&&*signatures.info().key.to_string()) {
+    // Reject revoked signature accounts and VAAs, falling back to the legacy entries until the
+    // Denylist has been created.
+    let key = signatures.info().key.to_bytes();
+    let revoked = if denylist.is_initialized() {
+        denylist.contains(&key) || denylist.contains(&signatures.hash) || denylist.contains(hash)
+    } else {
+        legacy_denylist_contains(&key)
+    };
+    if revoked {
         return Err(VAAInvalid.into());
     }
 
@@ -215,6 +274,30 @@ fn check_integrity<'r>(
     vaa: &PostVAAData,
     signatures: &SignatureSet<'r, { AccountState::Initialized }>,
 ) -> Result<()> {
+    // The hash currently stored in the signature account is expected to be the hash of the VAA
+    // body, binding that set of signatures to this VAA.
+    if signatures.hash != body_hash(vaa)? {
+        return Err(ProgramError::InvalidAccountData.into());
+    }
+    Ok(())
+}
+
+#[inline(always)]
+fn check_batch_integrity<'r>(
+    hash: &[u8; 32],
+    proof: &[[u8; 32]],
+    signatures: &SignatureSet<'r, { AccountState::Initialized }>,
+) -> Result<()> {
+    // The hash stored in the signature account is expected to be the digest of a batch, which the
+    // proof must show includes this VAA.
+    if signatures.hash != batch::digest(&batch::root_from_proof(hash, proof)) {
+        return Err(ProgramError::InvalidAccountData.into());
+    }
+    Ok(())
+}
+
+/// Hash the VAA body, this is the hash guardians sign and the seed of the `PostedVAA` address.
+pub(crate) fn body_hash(vaa: &PostVAAData) -> Result<[u8; 32]> {
     // Serialize the VAA body into an array of bytes.
     let body = {
         let mut v = Cursor::new(Vec::new());
@@ -228,17 +311,8 @@ fn check_integrity<'r>(
         v.into_inner()
     };
 
-    // Hash this body, which is expected to be the same as the hash currently stored in the
-    // signature account, binding that set of signatures to this VAA.
-    let body_hash: [u8; 32] = {
-        let mut h = sha3::Keccak256::default();
-        h.write(body.as_slice())
-            .map_err(|_| ProgramError::InvalidArgument)?;
-        h.finalize().into()
-    };
-
-    if signatures.hash != body_hash {
-        return Err(ProgramError::InvalidAccountData.into());
-    }
-    Ok(())
+    let mut h = sha3::Keccak256::default();
+    h.write(body.as_slice())
+        .map_err(|_| ProgramError::InvalidArgument)?;
+    Ok(h.finalize().into())
 }
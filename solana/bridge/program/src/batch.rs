@@ -0,0 +1,111 @@
+//! Batches let guardians sign many VAAs at once, so relayers only need to verify one set of
+//! signatures for the whole batch. Guardians sign a digest of the root of a Merkle tree over the
+//! VAA body hashes, and each VAA is then posted with a proof of its inclusion in the tree.
+//!
+//! Leaves and nodes are hashed with different prefixes, so that a node can never be passed off as
+//! a VAA. Each pair is sorted before hashing, so proofs need no left/right markers. A node without
+//! a sibling is carried up to the next level unchanged.
+//!
+//! The signed digest is `keccak256(BATCH_DIGEST_PREFIX || root)`. Its preimage is shorter than any
+//! VAA body, so signatures over a batch can never be used to post a VAA directly, and the other
+//! way around.
+
+use sha3::Digest;
+
+/// Prefix of the batch digest, which must stay short enough to keep the digest preimage shorter
+/// than the 51 bytes of an empty VAA body.
+pub const BATCH_DIGEST_PREFIX: &[u8] = b"wormhole-batch";
+
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+fn keccak(parts: &[&[u8]]) -> [u8; 32] {
+    let mut h = sha3::Keccak256::default();
+    for part in parts {
+        h.update(part);
+    }
+    h.finalize().into()
+}
+
+/// Hash a VAA body hash into a leaf of the batch tree.
+pub fn leaf(body_hash: &[u8; 32]) -> [u8; 32] {
+    keccak(&[&[LEAF_PREFIX], body_hash])
+}
+
+/// Hash two sibling nodes into their parent.
+pub fn node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    keccak(&[&[NODE_PREFIX], left, right])
+}
+
+/// The digest guardians sign for a batch with the given root.
+pub fn digest(root: &[u8; 32]) -> [u8; 32] {
+    keccak(&[BATCH_DIGEST_PREFIX, root])
+}
+
+/// Compute the root of a batch from one of its VAA body hashes and the proof of its inclusion.
+pub fn root_from_proof(body_hash: &[u8; 32], proof: &[[u8; 32]]) -> [u8; 32] {
+    proof
+        .iter()
+        .fold(leaf(body_hash), |hash, sibling| node(&hash, sibling))
+}
+
+/// Compute the root of a batch of VAA body hashes.
+pub fn root(body_hashes: &[[u8; 32]]) -> [u8; 32] {
+    let mut level: Vec<[u8; 32]> = body_hashes.iter().map(leaf).collect();
+    while level.len() > 1 {
+        level = parent_level(&level);
+    }
+    level.first().copied().unwrap_or_default()
+}
+
+/// Build the proof that the body hash at `index` is included in a batch.
+pub fn proof(body_hashes: &[[u8; 32]], mut index: usize) -> Vec<[u8; 32]> {
+    let mut proof = Vec::new();
+    let mut level: Vec<[u8; 32]> = body_hashes.iter().map(leaf).collect();
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        level = parent_level(&level);
+        index /= 2;
+    }
+    proof
+}
+
+fn parent_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [a, b] => node(a, b),
+            [a] => *a,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_proofs() {
+        for size in 1..=9 {
+            let hashes: Vec<[u8; 32]> = (0..size).map(|i| [i as u8; 32]).collect();
+            let root = root(&hashes);
+            for (index, hash) in hashes.iter().enumerate() {
+                let proof = proof(&hashes, index);
+                assert_eq!(root_from_proof(hash, &proof), root);
+
+                // Proofs only hold for the VAA they were built for.
+                let other = [0xff; 32];
+                assert_ne!(root_from_proof(&other, &proof), root);
+            }
+        }
+    }
+
+    #[test]
+    fn test_digest_preimage_shorter_than_vaa_body() {
+        assert!(BATCH_DIGEST_PREFIX.len() + 32 < 51);
+    }
+}
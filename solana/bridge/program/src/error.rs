@@ -26,6 +26,13 @@ pub enum Error {
     VAAInvalid,
     InvalidPayloadLength,
     EmitterChanged,
+    InvalidRefundAccount,
+    PostedVAATooRecent,
+    InvalidQuorum,
+    BridgePaused,
+    PauseAllowlistTooLong,
+    DenylistFull,
+    BridgeNotMigrated,
 }
 
 /// Errors thrown by the program will bubble up to the solitaire wrapper, which needs a way to
@@ -0,0 +1,96 @@
+//! Structured events logged by the bridge whenever its state changes, so that off-chain watchers
+//! do not need to scrape free-form log output.
+//!
+//! Each event is logged on its own line as `EVENT_LOG_PREFIX` followed by the base64 encoding of
+//! the Borsh serialized `VersionedEvent`. New events are only ever appended to `Event`, and any
+//! change to an existing event is released as a new version.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solana_program::{
+    msg,
+    pubkey::Pubkey,
+};
+
+use crate::accounts::AllowedEmitter;
+
+pub const EVENT_LOG_PREFIX: &str = "Wormhole event: ";
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub enum VersionedEvent {
+    V1(Event),
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub enum Event {
+    MessagePosted {
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+        nonce: u32,
+        consistency_level: u8,
+    },
+    VAAPosted {
+        posted_vaa: Pubkey,
+        guardian_set_index: u32,
+        emitter_chain: u16,
+        emitter_address: [u8; 32],
+        sequence: u64,
+        hash: [u8; 32],
+    },
+    GuardianSetUpgraded {
+        old_index: u32,
+        new_index: u32,
+    },
+    FeesSet {
+        fee: u64,
+    },
+    FeesTransferred {
+        recipient: Pubkey,
+        amount: u64,
+    },
+    ContractUpgraded {
+        new_contract: Pubkey,
+    },
+    QuorumSet {
+        numerator: u8,
+        denominator: u8,
+    },
+    Paused {
+        allowlist: Vec<AllowedEmitter>,
+    },
+    Unpaused,
+    Revoked {
+        entry: [u8; 32],
+    },
+    Unrevoked {
+        entry: [u8; 32],
+    },
+}
+
+impl Event {
+    /// Encode the event as it appears in the program log.
+    pub fn to_log(&self) -> String {
+        let event = VersionedEvent::V1(self.clone());
+        format!(
+            "{}{}",
+            EVENT_LOG_PREFIX,
+            base64::encode(event.try_to_vec().unwrap())
+        )
+    }
+
+    /// Decode an event from a line logged by `emit`, without the runtime's `Program log: ` prefix.
+    pub fn from_log(line: &str) -> Option<Event> {
+        let data = base64::decode(line.strip_prefix(EVENT_LOG_PREFIX)?).ok()?;
+        match VersionedEvent::try_from_slice(&data).ok()? {
+            VersionedEvent::V1(event) => Some(event),
+        }
+    }
+}
+
+/// Log an event.
+pub fn emit(event: Event) {
+    msg!("{}", event.to_log());
+}
@@ -27,20 +27,33 @@ use crate::{
         Bridge,
         Claim,
         ClaimDerivationData,
+        Denylist,
         FeeCollector,
         GuardianSet,
         GuardianSetDerivationData,
         PostedVAA,
         PostedVAADerivationData,
+        PostedVAAPayer,
         Sequence,
         SequenceDerivationData,
     },
     types::ConsistencyLevel,
+    CloseBatchedSignatureSetData,
+    ClosePostedVAAData,
+    CloseSignatureSetData,
     InitializeData,
+    InitializeDenylistData,
+    MigrateBridgeData,
+    PauseData,
+    PostBatchedVAAData,
     PostMessageData,
     PostVAAData,
+    RevokeData,
     SetFeesData,
+    SetQuorumData,
     TransferFeesData,
+    UnpauseData,
+    UnrevokeData,
     UpgradeContractData,
     UpgradeGuardianSetData,
     VerifySignaturesData,
@@ -84,6 +97,49 @@ pub fn initialize(
     })
 }
 
+pub fn initialize_denylist(program_id: Pubkey, payer: Pubkey) -> Instruction {
+    let denylist = Denylist::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(denylist, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::InitializeDenylist,
+            InitializeDenylistData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn migrate_bridge(program_id: Pubkey, payer: Pubkey) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(bridge, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::MigrateBridge,
+            MigrateBridgeData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
 pub fn post_message(
     program_id: Pubkey,
     payer: Pubkey,
@@ -220,6 +276,11 @@ pub fn post_vaa(
 
     let message =
         PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &program_id);
+    let denylist = Denylist::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
+    let posted_vaa_payer = PostedVAAPayer::<'_, { AccountState::MaybeInitialized }>::key(
+        msg_derivation_data,
+        &program_id,
+    );
 
     Instruction {
         program_id,
@@ -231,6 +292,8 @@ pub fn post_vaa(
             AccountMeta::new(message, false),
             AccountMeta::new(payer, true),
             AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(denylist, false),
+            AccountMeta::new(posted_vaa_payer, false),
             AccountMeta::new_readonly(sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
         ],
@@ -241,6 +304,26 @@ pub fn post_vaa(
     }
 }
 
+/// Post a VAA whose signatures were verified as part of a batch, with the proof of its inclusion
+/// built by `batch::proof`.
+pub fn post_batched_vaa(
+    program_id: Pubkey,
+    payer: Pubkey,
+    signature_set: Pubkey,
+    vaa: PostVAAData,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    // Batched VAAs are posted with the same accounts as any other VAA.
+    let mut ix = post_vaa(program_id, payer, signature_set, vaa.clone());
+    ix.data = (
+        crate::instruction::Instruction::PostBatchedVAA,
+        PostBatchedVAAData { vaa, proof },
+    )
+        .try_to_vec()
+        .unwrap();
+    ix
+}
+
 pub fn upgrade_contract(
     program_id: Pubkey,
     payer: Pubkey,
@@ -380,6 +463,169 @@ pub fn set_fees(
     }
 }
 
+pub fn set_quorum(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+) -> Instruction {
+    governance_instruction(
+        program_id,
+        payer,
+        message,
+        emitter,
+        sequence,
+        (crate::instruction::Instruction::SetQuorum, SetQuorumData {})
+            .try_to_vec()
+            .unwrap(),
+    )
+}
+
+pub fn pause(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+) -> Instruction {
+    governance_instruction(
+        program_id,
+        payer,
+        message,
+        emitter,
+        sequence,
+        (crate::instruction::Instruction::Pause, PauseData {})
+            .try_to_vec()
+            .unwrap(),
+    )
+}
+
+pub fn unpause(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+) -> Instruction {
+    governance_instruction(
+        program_id,
+        payer,
+        message,
+        emitter,
+        sequence,
+        (crate::instruction::Instruction::Unpause, UnpauseData {})
+            .try_to_vec()
+            .unwrap(),
+    )
+}
+
+pub fn revoke(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+) -> Instruction {
+    denylist_instruction(
+        program_id,
+        payer,
+        message,
+        emitter,
+        sequence,
+        (crate::instruction::Instruction::Revoke, RevokeData {})
+            .try_to_vec()
+            .unwrap(),
+    )
+}
+
+pub fn unrevoke(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+) -> Instruction {
+    denylist_instruction(
+        program_id,
+        payer,
+        message,
+        emitter,
+        sequence,
+        (crate::instruction::Instruction::Unrevoke, UnrevokeData {})
+            .try_to_vec()
+            .unwrap(),
+    )
+}
+
+/// Governance actions that update the Denylist share the same account layout.
+fn denylist_instruction(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+    data: Vec<u8>,
+) -> Instruction {
+    let denylist = Denylist::<'_, { AccountState::Initialized }>::key(None, &program_id);
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_SOLANA,
+            sequence,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(denylist, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data,
+    }
+}
+
+/// Governance actions that only update the Bridge account share the same account layout.
+fn governance_instruction(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+    data: Vec<u8>,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_SOLANA,
+            sequence,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(bridge, false),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data,
+    }
+}
+
 pub fn transfer_fees(
     program_id: Pubkey,
     payer: Pubkey,
@@ -423,6 +669,99 @@ pub fn transfer_fees(
     }
 }
 
+pub fn close_signature_set(
+    program_id: Pubkey,
+    payer: Pubkey,
+    signature_set: Pubkey,
+    vaa: &PostVAAData,
+) -> Instruction {
+    let posted_vaa = PostedVAA::<'_, { AccountState::Initialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(vaa).to_vec(),
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(signature_set, false),
+            AccountMeta::new_readonly(posted_vaa, false),
+            AccountMeta::new(payer, false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::CloseSignatureSet,
+            CloseSignatureSetData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Close a signature set verified for a batch, using any posted VAA of the batch and the proof of
+/// its inclusion.
+pub fn close_batched_signature_set(
+    program_id: Pubkey,
+    payer: Pubkey,
+    signature_set: Pubkey,
+    vaa: &PostVAAData,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    let posted_vaa = PostedVAA::<'_, { AccountState::Initialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(vaa).to_vec(),
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(signature_set, false),
+            AccountMeta::new_readonly(posted_vaa, false),
+            AccountMeta::new(payer, true),
+        ],
+
+        data: (
+            crate::instruction::Instruction::CloseBatchedSignatureSet,
+            CloseBatchedSignatureSetData { proof },
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn close_posted_vaa(program_id: Pubkey, payer: Pubkey, vaa: &PostVAAData) -> Instruction {
+    let derivation_data = PostedVAADerivationData {
+        payload_hash: hash_vaa(vaa).to_vec(),
+    };
+    let posted_vaa =
+        PostedVAA::<'_, { AccountState::Initialized }>::key(&derivation_data, &program_id);
+    let posted_vaa_payer =
+        PostedVAAPayer::<'_, { AccountState::Initialized }>::key(&derivation_data, &program_id);
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(posted_vaa, false),
+            AccountMeta::new(posted_vaa_payer, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::ClosePostedVAA,
+            ClosePostedVAAData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
 // Convert a full VAA structure into the serialization of its unique components, this structure is
 // what is hashed and verified by Guardians.
 pub fn serialize_vaa(vaa: &PostVAAData) -> Vec<u8> {
@@ -21,11 +21,14 @@ pub mod wasm;
 pub mod accounts;
 
 pub use accounts::{
+    AllowedEmitter,
     BridgeConfig,
     BridgeData,
     Claim,
     ClaimData,
     ClaimDerivationData,
+    Denylist,
+    DenylistData,
     FeeCollector,
     GuardianSet,
     GuardianSetData,
@@ -37,6 +40,7 @@ pub use accounts::{
     PostedMessageUnreliableData,
     PostedVAA,
     PostedVAAData,
+    Quorum,
     Sequence,
     SequenceDerivationData,
     SequenceTracker,
@@ -47,28 +51,60 @@ pub use accounts::{
 pub mod api;
 
 pub use api::{
+    close_batched_signature_set,
+    close_posted_vaa,
+    close_signature_set,
     initialize,
+    initialize_denylist,
+    migrate_bridge,
+    pause,
+    post_batched_vaa,
     post_message,
     post_message_unreliable,
     post_vaa,
+    revoke,
     set_fees,
+    set_quorum,
     transfer_fees,
+    unpause,
+    unrevoke,
     upgrade_contract,
     upgrade_guardian_set,
     verify_signatures,
+    CloseBatchedSignatureSet,
+    CloseBatchedSignatureSetData,
+    ClosePostedVAA,
+    ClosePostedVAAData,
+    CloseSignatureSet,
+    CloseSignatureSetData,
     Initialize,
     InitializeData,
+    InitializeDenylist,
+    InitializeDenylistData,
+    MigrateBridge,
+    MigrateBridgeData,
+    Pause,
+    PauseData,
+    PostBatchedVAAData,
     PostMessage,
     PostMessageData,
     PostMessageUnreliable,
     PostVAA,
     PostVAAData,
+    Revoke,
+    RevokeData,
     SetFees,
     SetFeesData,
+    SetQuorum,
+    SetQuorumData,
     Signature,
     TransferFees,
     TransferFeesData,
     UninitializedMessage,
+    Unpause,
+    UnpauseData,
+    Unrevoke,
+    UnrevokeData,
     UpgradeContract,
     UpgradeContractData,
     UpgradeGuardianSet,
@@ -77,7 +113,9 @@ pub use api::{
     VerifySignaturesData,
 };
 
+pub mod batch;
 pub mod error;
+pub mod events;
 pub mod types;
 pub mod vaa;
 
@@ -99,4 +137,15 @@ solitaire! {
     UpgradeGuardianSet => upgrade_guardian_set,
     VerifySignatures   => verify_signatures,
     PostMessageUnreliable        => post_message_unreliable,
+    CloseSignatureSet            => close_signature_set,
+    ClosePostedVAA               => close_posted_vaa,
+    SetQuorum                    => set_quorum,
+    Pause                        => pause,
+    Unpause                      => unpause,
+    InitializeDenylist           => initialize_denylist,
+    Revoke                       => revoke,
+    PostBatchedVAA               => post_batched_vaa,
+    MigrateBridge                => migrate_bridge,
+    Unrevoke                     => unrevoke,
+    CloseBatchedSignatureSet     => close_batched_signature_set,
 }
@@ -1,4 +1,5 @@
 use crate::{
+    accounts::AllowedEmitter,
     api::ForeignAddress,
     vaa::{
         DeserializeGovernancePayload,
@@ -233,3 +234,208 @@ impl SerializeGovernancePayload for GovernancePayloadTransferFees {
 
 impl DeserializeGovernancePayload for GovernancePayloadTransferFees {
 }
+
+pub struct GovernancePayloadSetQuorum {
+    // Fraction of guardians required to sign a VAA
+    pub numerator: u8,
+    pub denominator: u8,
+}
+
+impl SerializePayload for GovernancePayloadSetQuorum {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        use byteorder::WriteBytesExt;
+        v.write_u8(self.numerator)?;
+        v.write_u8(self.denominator)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetQuorum
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let numerator = c.read_u8()?;
+        let denominator = c.read_u8()?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetQuorum {
+            numerator,
+            denominator,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetQuorum {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 5;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetQuorum {
+}
+
+pub struct GovernancePayloadPause {
+    // Emitters that may keep posting while paused
+    pub allowlist: Vec<AllowedEmitter>,
+}
+
+impl SerializePayload for GovernancePayloadPause {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        use byteorder::WriteBytesExt;
+        v.write_u8(self.allowlist.len() as u8)?;
+        for emitter in self.allowlist.iter() {
+            v.write_u16::<BigEndian>(emitter.chain)?;
+            v.write_all(&emitter.address)?;
+        }
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadPause
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let allowlist_len = c.read_u8()?;
+        let mut allowlist = Vec::with_capacity(allowlist_len as usize);
+        for _ in 0..allowlist_len {
+            let chain = c.read_u16::<BigEndian>()?;
+            let mut address = ForeignAddress::default();
+            c.read_exact(&mut address)?;
+            allowlist.push(AllowedEmitter { chain, address });
+        }
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadPause { allowlist })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadPause {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 6;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadPause {
+}
+
+pub struct GovernancePayloadUnpause {}
+
+impl SerializePayload for GovernancePayloadUnpause {
+    fn serialize<W: Write>(&self, _v: &mut W) -> std::result::Result<(), SolitaireError> {
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadUnpause
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadUnpause {})
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadUnpause {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 7;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadUnpause {
+}
+
+pub struct GovernancePayloadRevoke {
+    // Signature set address or VAA body hash to reject
+    pub entry: [u8; 32],
+}
+
+impl SerializePayload for GovernancePayloadRevoke {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_all(&self.entry)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadRevoke
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let mut entry = [0u8; 32];
+        c.read_exact(&mut entry)?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadRevoke { entry })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadRevoke {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 8;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadRevoke {
+}
+
+pub struct GovernancePayloadUnrevoke {
+    // Signature set address or VAA body hash to accept again
+    pub entry: [u8; 32],
+}
+
+impl SerializePayload for GovernancePayloadUnrevoke {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_all(&self.entry)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadUnrevoke
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let mut entry = [0u8; 32];
+        c.read_exact(&mut entry)?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadUnrevoke { entry })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadUnrevoke {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 9;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadUnrevoke {
+}
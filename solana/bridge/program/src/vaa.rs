@@ -173,7 +173,92 @@ impl VAA {
     pub const HEADER_LEN: usize = 6;
     pub const SIGNATURE_LEN: usize = 66;
 
+    /// Parse a VAA with `wormhole_core`, so that the bridge and off-chain tooling agree exactly on
+    /// which VAAs are valid.
     pub fn deserialize(data: &[u8]) -> std::result::Result<VAA, std::io::Error> {
+        use wormhole_core::{
+            ParseError,
+            ParseErrorKind,
+            WormholeError,
+        };
+
+        let vaa = wormhole_core::VAA::from_bytes(data).map_err(|e| {
+            let kind = match e {
+                WormholeError::ParseError(ParseError {
+                    kind: ParseErrorKind::Truncated { .. },
+                    ..
+                }) => std::io::ErrorKind::UnexpectedEof,
+                _ => std::io::ErrorKind::InvalidData,
+            };
+            std::io::Error::new(kind, e.to_string())
+        })?;
+
+        Ok(vaa.into())
+    }
+}
+
+impl From<wormhole_core::VAA> for VAA {
+    fn from(vaa: wormhole_core::VAA) -> Self {
+        VAA {
+            version: vaa.version,
+            guardian_set_index: vaa.guardian_set_index,
+            signatures: vaa
+                .signatures
+                .iter()
+                .map(|s| VAASignature {
+                    guardian_index: s[0],
+                    signature: s[1..].to_vec(),
+                })
+                .collect(),
+            timestamp: vaa.timestamp,
+            nonce: vaa.nonce,
+            emitter_chain: vaa.emitter_chain.into(),
+            emitter_address: vaa.emitter_address,
+            sequence: vaa.sequence,
+            consistency_level: vaa.consistency_level,
+            payload: vaa.payload,
+        }
+    }
+}
+
+impl From<VAA> for PostVAAData {
+    fn from(vaa: VAA) -> Self {
+        PostVAAData {
+            version: vaa.version,
+            guardian_set_index: vaa.guardian_set_index,
+            timestamp: vaa.timestamp,
+            nonce: vaa.nonce,
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+            sequence: vaa.sequence,
+            consistency_level: vaa.consistency_level,
+            payload: vaa.payload,
+        }
+    }
+}
+
+impl From<wormhole_core::VAA> for PostVAAData {
+    fn from(vaa: wormhole_core::VAA) -> Self {
+        PostVAAData {
+            version: vaa.version,
+            guardian_set_index: vaa.guardian_set_index,
+            timestamp: vaa.timestamp,
+            nonce: vaa.nonce,
+            emitter_chain: vaa.emitter_chain.into(),
+            emitter_address: vaa.emitter_address,
+            sequence: vaa.sequence,
+            consistency_level: vaa.consistency_level,
+            payload: vaa.payload,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The parser this module used before delegating to `wormhole_core`, kept to check parity.
+    fn legacy_deserialize(data: &[u8]) -> std::result::Result<VAA, std::io::Error> {
         let mut rdr = Cursor::new(data);
 
         let version = rdr.read_u8()?;
@@ -219,20 +304,47 @@ impl VAA {
             payload,
         })
     }
-}
 
-impl From<VAA> for PostVAAData {
-    fn from(vaa: VAA) -> Self {
-        PostVAAData {
-            version: vaa.version,
-            guardian_set_index: vaa.guardian_set_index,
-            timestamp: vaa.timestamp,
-            nonce: vaa.nonce,
-            emitter_chain: vaa.emitter_chain,
-            emitter_address: vaa.emitter_address,
-            sequence: vaa.sequence,
-            consistency_level: vaa.consistency_level,
-            payload: vaa.payload,
+    fn assert_same(a: &VAA, b: &VAA) {
+        assert_eq!(a.version, b.version);
+        assert_eq!(a.guardian_set_index, b.guardian_set_index);
+        assert_eq!(a.signatures.len(), b.signatures.len());
+        for (a, b) in a.signatures.iter().zip(b.signatures.iter()) {
+            assert_eq!(a.guardian_index, b.guardian_index);
+            assert_eq!(a.signature, b.signature);
+        }
+        assert_eq!(a.timestamp, b.timestamp);
+        assert_eq!(a.nonce, b.nonce);
+        assert_eq!(a.emitter_chain, b.emitter_chain);
+        assert_eq!(a.emitter_address, b.emitter_address);
+        assert_eq!(a.sequence, b.sequence);
+        assert_eq!(a.consistency_level, b.consistency_level);
+        assert_eq!(a.payload, b.payload);
+    }
+
+    #[test]
+    fn test_parse_vaa_parity() {
+        let vaa = hex::decode("01000000000100b072505b5b999c1d08905c02e2b6b2832ef72c0ba6c8db4f77fe457ef2b3d053410b1e92a9194d9210df24d987ac83d7b6f0c21ce90f8bc1869de0898bda7e980100000001000000010001000000000000000000000000000000000000000000000000000000000000000400000000013c1bfa00000000000000000000000000000000000000000000546f6b656e42726964676501000000013b26409f8aaded3f5ddca184695aa6a0fa829b0c85caf84856324896d214ca98").unwrap();
+        assert_same(
+            &VAA::deserialize(&vaa).unwrap(),
+            &legacy_deserialize(&vaa).unwrap(),
+        );
+
+        // Every truncation must be accepted or rejected by both parsers alike.
+        for len in 0..vaa.len() {
+            match (
+                VAA::deserialize(&vaa[..len]),
+                legacy_deserialize(&vaa[..len]),
+            ) {
+                (Ok(new), Ok(old)) => assert_same(&new, &old),
+                (Err(new), Err(old)) => assert_eq!(new.kind(), old.kind()),
+                (new, old) => panic!(
+                    "parsers disagree at length {}: {:?} {:?}",
+                    len,
+                    new.err(),
+                    old.err()
+                ),
+            }
         }
     }
 }
@@ -32,11 +32,19 @@ use crate::{
     },
     instructions::{
         hash_vaa,
+        initialize_denylist,
+        migrate_bridge,
+        pause,
+        post_batched_vaa,
         post_message,
         post_message_unreliable,
         post_vaa,
+        revoke,
         set_fees,
+        set_quorum,
         transfer_fees,
+        unpause,
+        unrevoke,
         upgrade_contract,
         upgrade_guardian_set,
         verify_signatures,
@@ -55,6 +63,24 @@ use crate::{
 use byteorder::LittleEndian;
 use wasm_bindgen::prelude::*;
 
+#[wasm_bindgen]
+pub fn initialize_denylist_ix(program_id: String, payer: String) -> JsValue {
+    let ix = initialize_denylist(
+        Pubkey::from_str(program_id.as_str()).unwrap(),
+        Pubkey::from_str(payer.as_str()).unwrap(),
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn migrate_bridge_ix(program_id: String, payer: String) -> JsValue {
+    let ix = migrate_bridge(
+        Pubkey::from_str(program_id.as_str()).unwrap(),
+        Pubkey::from_str(payer.as_str()).unwrap(),
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
 #[wasm_bindgen]
 pub fn post_message_ix(
     program_id: String,
@@ -139,6 +165,34 @@ pub fn post_vaa_ix(
     return JsValue::from_serde(&ix).unwrap();
 }
 
+/// The proof is passed as its 32 byte hashes concatenated, leaf end first.
+#[wasm_bindgen]
+pub fn post_batched_vaa_ix(
+    program_id: String,
+    payer: String,
+    signature_set: String,
+    vaa: Vec<u8>,
+    proof: Vec<u8>,
+) -> JsValue {
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let proof = proof
+        .chunks_exact(32)
+        .map(|hash| {
+            let mut h = [0u8; 32];
+            h.copy_from_slice(hash);
+            h
+        })
+        .collect();
+    let ix = post_batched_vaa(
+        Pubkey::from_str(program_id.as_str()).unwrap(),
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        Pubkey::from_str(signature_set.as_str()).unwrap(),
+        vaa.into(),
+        proof,
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
 #[wasm_bindgen]
 pub fn update_guardian_set_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
     let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
@@ -183,6 +237,106 @@ pub fn set_fees_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
     return JsValue::from_serde(&ix).unwrap();
 }
 
+#[wasm_bindgen]
+pub fn set_quorum_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &program_id,
+    );
+    let ix = set_quorum(
+        program_id,
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        message_key,
+        Pubkey::new(&vaa.emitter_address),
+        vaa.sequence,
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn pause_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &program_id,
+    );
+    let ix = pause(
+        program_id,
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        message_key,
+        Pubkey::new(&vaa.emitter_address),
+        vaa.sequence,
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn unpause_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &program_id,
+    );
+    let ix = unpause(
+        program_id,
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        message_key,
+        Pubkey::new(&vaa.emitter_address),
+        vaa.sequence,
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn revoke_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &program_id,
+    );
+    let ix = revoke(
+        program_id,
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        message_key,
+        Pubkey::new(&vaa.emitter_address),
+        vaa.sequence,
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn unrevoke_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &program_id,
+    );
+    let ix = unrevoke(
+        program_id,
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        message_key,
+        Pubkey::new(&vaa.emitter_address),
+        vaa.sequence,
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
 #[wasm_bindgen]
 pub fn transfer_fees_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
     let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
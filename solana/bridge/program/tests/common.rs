@@ -10,15 +10,19 @@ use libsecp256k1::{
 };
 use sha3::Digest;
 use solana_program::{
+    clock::Clock,
     instruction::Instruction,
     pubkey::Pubkey,
+    rent::Rent,
     system_instruction,
 };
 use solana_program_test::{
     BanksClient,
     ProgramTest,
+    ProgramTestContext,
 };
 use solana_sdk::{
+    account::AccountSharedData,
     commitment_config::CommitmentLevel,
     secp256k1_instruction::new_secp256k1_instruction,
     signature::{
@@ -39,7 +43,10 @@ use std::{
 };
 
 use bridge::{
-    accounts::FeeCollector,
+    accounts::{
+        Bridge,
+        FeeCollector,
+    },
     instruction,
     instructions,
     types::ConsistencyLevel,
@@ -47,7 +54,10 @@ use bridge::{
     VerifySignaturesData,
 };
 
-use solitaire::processors::seeded::Seeded;
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountState,
+};
 
 pub use helpers::*;
 
@@ -75,6 +85,20 @@ mod helpers {
     /// Initialize the test environment, spins up a solana-test-validator in the background so that
     /// each test has a fresh environment to work within.
     pub async fn setup() -> (BanksClient, Keypair, Pubkey) {
+        let (builder, program) = program_test();
+        let (client, payer, _) = builder.start().await;
+
+        (client, payer, program)
+    }
+
+    /// Like `setup`, but returns the full test context for tests that need to manipulate the
+    /// bank directly, such as moving the clock forward.
+    pub async fn setup_with_context() -> (ProgramTestContext, Pubkey) {
+        let (builder, program) = program_test();
+        (builder.start_with_context().await, program)
+    }
+
+    fn program_test() -> (ProgramTest, Pubkey) {
         let program = env::var("BRIDGE_PROGRAM")
             .unwrap_or_else(|_| "Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o".to_string())
             .parse::<Pubkey>()
@@ -85,9 +109,29 @@ mod helpers {
             processor!(instruction::solitaire),
         );
 
-        let (client, payer, _) = builder.start().await;
+        (builder, program)
+    }
 
-        (client, payer, program)
+    /// Rewrite the Bridge account in the layout it had before the quorum and pause state were
+    /// added, which ended after the config. Bridges deployed before then still have it.
+    pub async fn truncate_bridge(context: &mut ProgramTestContext, program: &Pubkey) {
+        let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
+        let mut account = context
+            .banks_client
+            .get_account(bridge_key)
+            .await
+            .unwrap()
+            .unwrap();
+        account.data.truncate(24);
+        account.lamports = Rent::default().minimum_balance(account.data.len());
+        context.set_account(&bridge_key, &AccountSharedData::from(account));
+    }
+
+    /// Move the cluster clock forward by the given number of seconds.
+    pub async fn advance_clock(context: &mut ProgramTestContext, seconds: i64) {
+        let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += seconds;
+        context.set_sysvar(&clock);
     }
 
     /// Wait for a single transaction to fully finalize, guaranteeing chain state has been
@@ -373,6 +417,99 @@ mod helpers {
         .await
     }
 
+    pub async fn post_batched_vaa(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        signature_set: Pubkey,
+        vaa: PostVAAData,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<(), TransportError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::post_batched_vaa(
+                *program,
+                payer.pubkey(),
+                signature_set,
+                vaa,
+                proof,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn close_signature_set(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        refund: Pubkey,
+        signature_set: Pubkey,
+        vaa: &PostVAAData,
+    ) -> Result<(), TransportError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::close_signature_set(
+                *program,
+                refund,
+                signature_set,
+                vaa,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn close_batched_signature_set(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        refund: &Keypair,
+        signature_set: Pubkey,
+        vaa: &PostVAAData,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<(), TransportError> {
+        execute(
+            client,
+            payer,
+            &[payer, refund],
+            &[instructions::close_batched_signature_set(
+                *program,
+                refund.pubkey(),
+                signature_set,
+                vaa,
+                proof,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn close_posted_vaa(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        refund: &Keypair,
+        vaa: &PostVAAData,
+    ) -> Result<(), TransportError> {
+        execute(
+            client,
+            payer,
+            &[payer, refund],
+            &[instructions::close_posted_vaa(
+                *program,
+                refund.pubkey(),
+                vaa,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn upgrade_guardian_set(
         client: &mut BanksClient,
@@ -455,6 +592,156 @@ mod helpers {
         .await
     }
 
+    pub async fn set_quorum(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+    ) -> Result<(), TransportError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_quorum(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn pause(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+    ) -> Result<(), TransportError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::pause(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn unpause(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+    ) -> Result<(), TransportError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::unpause(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn revoke(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+    ) -> Result<(), TransportError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::revoke(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn unrevoke(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+    ) -> Result<(), TransportError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::unrevoke(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn migrate_bridge(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+    ) -> Result<(), TransportError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::migrate_bridge(*program, payer.pubkey())],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn initialize_denylist(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+    ) -> Result<(), TransportError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::initialize_denylist(*program, payer.pubkey())],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     pub async fn transfer_fees(
         client: &mut BanksClient,
         program: &Pubkey,
@@ -10,6 +10,8 @@ use solana_program_test::{
 };
 use solana_sdk::{
     commitment_config::CommitmentLevel,
+    feature,
+    feature_set,
     signature::{
         Keypair,
         Signer,
@@ -22,8 +24,11 @@ use solitaire::{
 
 use bridge::{
     accounts::{
+        AllowedEmitter,
         Bridge,
         BridgeData,
+        Denylist,
+        DenylistData,
         FeeCollector,
         GuardianSet,
         GuardianSetData,
@@ -31,14 +36,22 @@ use bridge::{
         PostedVAA,
         PostedVAAData,
         PostedVAADerivationData,
+        PostedVAAPayer,
+        PostedVAAPayerData,
+        Quorum,
         SignatureSetData,
     },
     instructions,
     types::{
         ConsistencyLevel,
         GovernancePayloadGuardianSetChange,
+        GovernancePayloadPause,
+        GovernancePayloadRevoke,
         GovernancePayloadSetMessageFee,
+        GovernancePayloadSetQuorum,
         GovernancePayloadTransferFees,
+        GovernancePayloadUnpause,
+        GovernancePayloadUnrevoke,
         GovernancePayloadUpgrade,
     },
     SerializeGovernancePayload,
@@ -79,6 +92,12 @@ impl Sequencer {
 }
 
 async fn initialize() -> (Context, BanksClient, Keypair, Pubkey) {
+    let (mut client, payer, program) = common::setup().await;
+    let context = initialize_bridge(&mut client, &payer, program).await;
+    (context, client, payer, program)
+}
+
+async fn initialize_bridge(client: &mut BanksClient, payer: &Keypair, program: Pubkey) -> Context {
     let (public_keys, secret_keys) = common::generate_keys(6);
     let context = Context {
         public: public_keys,
@@ -87,8 +106,6 @@ async fn initialize() -> (Context, BanksClient, Keypair, Pubkey) {
             sequences: std::collections::HashMap::new(),
         },
     };
-    let (mut client, payer, program) = common::setup().await;
-
     // Use a timestamp from a few seconds earlier for testing to simulate thread::sleep();
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -96,10 +113,10 @@ async fn initialize() -> (Context, BanksClient, Keypair, Pubkey) {
         .as_secs()
         - 10;
 
-    common::initialize(&mut client, program, &payer, &context.public, 500)
+    common::initialize(client, program, payer, &context.public, 500)
         .await
         .unwrap();
-    common::sync(&mut client, &payer).await;
+    common::sync(client, payer).await;
 
     // Verify the initial bridge state is as expected.
     let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program);
@@ -109,9 +126,8 @@ async fn initialize() -> (Context, BanksClient, Keypair, Pubkey) {
     );
 
     // Fetch account states.
-    let bridge: BridgeData = common::get_account_data(&mut client, bridge_key).await;
-    let guardian_set: GuardianSetData =
-        common::get_account_data(&mut client, guardian_set_key).await;
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    let guardian_set: GuardianSetData = common::get_account_data(client, guardian_set_key).await;
 
     // Bridge Config should be as expected.
     assert_eq!(bridge.guardian_set_index, 0);
@@ -123,7 +139,7 @@ async fn initialize() -> (Context, BanksClient, Keypair, Pubkey) {
     assert_eq!(guardian_set.keys, context.public);
     assert!(guardian_set.creation_time as u64 > now);
 
-    (context, client, payer, program)
+    context
 }
 
 #[tokio::test]
@@ -302,6 +318,253 @@ async fn bridge_messages() {
     }
 }
 
+#[tokio::test]
+async fn close_signature_set_and_posted_vaa() {
+    // Uses the full test context so the clock can be moved past the minimum posted VAA age.
+    let (mut test_context, program) = common::setup_with_context().await;
+    let (ref mut client, ref payer, ref program) = (
+        test_context.banks_client.clone(),
+        Keypair::from_bytes(&test_context.payer.to_bytes()).unwrap(),
+        program,
+    );
+    let mut context = initialize_bridge(client, payer, *program).await;
+
+    let emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, b"close".to_vec(), nonce, sequence, 0, 1);
+
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+
+    // Signature sets cannot be closed until their VAA is posted.
+    assert!(common::close_signature_set(
+        client,
+        program,
+        payer,
+        payer.pubkey(),
+        signature_set,
+        &vaa
+    )
+    .await
+    .is_err());
+
+    common::post_vaa(client, program, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    common::sync(client, payer).await;
+
+    // Both accounts record who paid for them.
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    let payer_key = PostedVAAPayer::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    let posted_message: PostedVAAData = common::get_account_data(client, message_key).await;
+    let record: PostedVAAPayerData = common::get_account_data(client, payer_key).await;
+    let signatures: SignatureSetData = common::get_account_data(client, signature_set).await;
+    assert_eq!(record.payer, payer.pubkey());
+    assert_ne!(posted_message.message.submission_time, 0);
+    assert_eq!(signatures.payer, payer.pubkey());
+
+    // Refunds can only go to the original payer.
+    assert!(common::close_signature_set(
+        client,
+        program,
+        payer,
+        Keypair::new().pubkey(),
+        signature_set,
+        &vaa
+    )
+    .await
+    .is_err());
+
+    let rent = common::get_account_balance(client, signature_set).await;
+    let balance = common::get_account_balance(client, payer.pubkey()).await;
+    common::close_signature_set(client, program, payer, payer.pubkey(), signature_set, &vaa)
+        .await
+        .unwrap();
+    common::sync(client, payer).await;
+
+    assert!(client.get_account(signature_set).await.unwrap().is_none());
+    assert!(common::get_account_balance(client, payer.pubkey()).await > balance + rent / 2);
+
+    // The posted VAA is still needed by consumers, and cannot be closed until it is old enough.
+    assert!(
+        common::close_posted_vaa(client, program, payer, payer, &vaa)
+            .await
+            .is_err()
+    );
+    let posted_message: PostedVAAData = common::get_account_data(client, message_key).await;
+    assert_eq!(posted_message.message.payload, b"close".to_vec());
+
+    common::advance_clock(&mut test_context, 31 * 24 * 60 * 60).await;
+
+    // Once old enough, it can still only be closed by its payer.
+    assert!(
+        common::close_posted_vaa(client, program, payer, &Keypair::new(), &vaa)
+            .await
+            .is_err()
+    );
+
+    let rent = common::get_account_balance(client, message_key).await;
+    let balance = common::get_account_balance(client, payer.pubkey()).await;
+    common::close_posted_vaa(client, program, payer, payer, &vaa)
+        .await
+        .unwrap();
+    common::sync(client, payer).await;
+
+    assert!(client.get_account(message_key).await.unwrap().is_none());
+    assert!(client.get_account(payer_key).await.unwrap().is_none());
+    assert!(common::get_account_balance(client, payer.pubkey()).await > balance + rent / 2);
+}
+
+#[tokio::test]
+async fn post_batched_vaas() {
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+
+    let emitter = Keypair::new();
+    let mut vaas = Vec::new();
+    let mut bodies = Vec::new();
+    for i in 0..3u8 {
+        let nonce = rand::thread_rng().gen();
+        let sequence = context.seq.next(emitter.pubkey().to_bytes());
+        let (vaa, body, _body_hash) =
+            common::generate_vaa(&emitter, vec![i; 8], nonce, sequence, 0, 1);
+        vaas.push(vaa);
+        bodies.push(body);
+    }
+
+    // Guardians sign the batch once, rather than each VAA.
+    let digest = bridge::batch::digest(&bridge::batch::root(&bodies));
+    let signature_set =
+        common::verify_signatures(client, program, payer, digest, &context.secret, 0)
+            .await
+            .unwrap();
+
+    // A batch signature set can never be used to post a VAA directly.
+    assert!(
+        common::post_vaa(client, program, payer, signature_set, vaas[0].clone())
+            .await
+            .is_err()
+    );
+
+    // Proofs only hold for the VAA they were built for.
+    assert!(common::post_batched_vaa(
+        client,
+        program,
+        payer,
+        signature_set,
+        vaas[0].clone(),
+        bridge::batch::proof(&bodies, 1),
+    )
+    .await
+    .is_err());
+
+    // VAAs outside of the batch are rejected.
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let (outsider, _, _) = common::generate_vaa(&emitter, vec![9; 8], 0, sequence, 0, 1);
+    assert!(common::post_batched_vaa(
+        client,
+        program,
+        payer,
+        signature_set,
+        outsider,
+        bridge::batch::proof(&bodies, 0),
+    )
+    .await
+    .is_err());
+
+    for (index, vaa) in vaas.iter().enumerate() {
+        common::post_batched_vaa(
+            client,
+            program,
+            payer,
+            signature_set,
+            vaa.clone(),
+            bridge::batch::proof(&bodies, index),
+        )
+        .await
+        .unwrap();
+        common::sync(client, payer).await;
+
+        // Each VAA is posted at the address derived from its own body.
+        let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+            &PostedVAADerivationData {
+                payload_hash: bodies[index].to_vec(),
+            },
+            program,
+        );
+        let posted_message: PostedVAAData = common::get_account_data(client, message_key).await;
+        assert_eq!(posted_message.message.payload, vaa.payload);
+        assert_eq!(posted_message.message.sequence, vaa.sequence);
+        assert_eq!(posted_message.message.emitter_address, vaa.emitter_address);
+    }
+
+    // Batch signature sets are not over any single VAA, so are closed with a proof instead.
+    assert!(common::close_signature_set(
+        client,
+        program,
+        payer,
+        payer.pubkey(),
+        signature_set,
+        &vaas[0]
+    )
+    .await
+    .is_err());
+    assert!(common::close_batched_signature_set(
+        client,
+        program,
+        payer,
+        payer,
+        signature_set,
+        &vaas[0],
+        bridge::batch::proof(&bodies, 1),
+    )
+    .await
+    .is_err());
+
+    // Only the payer can close it, as other VAAs of the batch may still need it.
+    assert!(common::close_batched_signature_set(
+        client,
+        program,
+        payer,
+        &Keypair::new(),
+        signature_set,
+        &vaas[2],
+        bridge::batch::proof(&bodies, 2),
+    )
+    .await
+    .is_err());
+
+    let rent = common::get_account_balance(client, signature_set).await;
+    let balance = common::get_account_balance(client, payer.pubkey()).await;
+    common::close_batched_signature_set(
+        client,
+        program,
+        payer,
+        payer,
+        signature_set,
+        &vaas[2],
+        bridge::batch::proof(&bodies, 2),
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+
+    assert!(client.get_account(signature_set).await.unwrap().is_none());
+    assert!(common::get_account_balance(client, payer.pubkey()).await > balance + rent / 2);
+}
+
 // Make sure that posting messages with account reuse works and only accepts messages with the same
 // length.
 #[tokio::test]
@@ -1202,6 +1465,567 @@ async fn free_fees() {
     }
 }
 
+/// Post a governance message and its VAA, returning the posted message and sequence used to
+/// execute the governance action.
+async fn post_governance_vaa(
+    context: &mut Context,
+    client: &mut BanksClient,
+    payer: &Keypair,
+    program: &Pubkey,
+    message: Vec<u8>,
+) -> (Pubkey, u64) {
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+
+    let (vaa, body, _body_hash) = common::generate_vaa(&emitter, message, nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+
+    (message_key, sequence)
+}
+
+#[tokio::test]
+async fn set_quorum() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let governance = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
+
+    // The bridge starts with a 2/3 quorum, requiring 5 of the 6 guardians.
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert!(bridge.quorum == Quorum::default());
+    assert_eq!(bridge.quorum.required_signatures(6), 5);
+
+    // A VAA signed by 4 guardians is rejected.
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, b"quorum".to_vec(), nonce, sequence, 0, 2);
+    let signature_set =
+        common::verify_signatures(client, program, payer, body, &context.secret[..4], 0)
+            .await
+            .unwrap();
+    assert!(
+        common::post_vaa(client, program, payer, signature_set, vaa.clone())
+            .await
+            .is_err()
+    );
+
+    // Invalid quorums are rejected by governance.
+    for (numerator, denominator) in [(1, 3), (3, 3), (0, 0)] {
+        let message = GovernancePayloadSetQuorum {
+            numerator,
+            denominator,
+        }
+        .try_to_vec()
+        .unwrap();
+        let (message_key, sequence) =
+            post_governance_vaa(context, client, payer, program, message).await;
+        assert!(common::set_quorum(
+            client,
+            program,
+            payer,
+            message_key,
+            governance.pubkey(),
+            sequence,
+        )
+        .await
+        .is_err());
+    }
+
+    // Lower the quorum to a simple majority.
+    let message = GovernancePayloadSetQuorum {
+        numerator: 1,
+        denominator: 2,
+    }
+    .try_to_vec()
+    .unwrap();
+    let (message_key, sequence) =
+        post_governance_vaa(context, client, payer, program, message).await;
+    common::set_quorum(
+        client,
+        program,
+        payer,
+        message_key,
+        governance.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert_eq!(bridge.quorum.numerator, 1);
+    assert_eq!(bridge.quorum.denominator, 2);
+
+    // The same signatures now reach consensus.
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn migrate_legacy_bridge() {
+    let (mut test_context, program) = common::setup_with_context().await;
+    let (ref mut client, ref payer, ref program) = (
+        test_context.banks_client.clone(),
+        Keypair::from_bytes(&test_context.payer.to_bytes()).unwrap(),
+        program,
+    );
+    let mut context = initialize_bridge(client, payer, *program).await;
+    let governance = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
+
+    // A Bridge account in the legacy layout loads with the default quorum and pause state.
+    common::truncate_bridge(&mut test_context, program).await;
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert!(bridge.legacy_layout);
+    assert_eq!(bridge.config.fee, 500);
+    assert!(bridge.quorum == Quorum::default());
+    assert!(!bridge.paused);
+
+    // Messages and VAAs are still posted, writing the account back in the same layout.
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let message = b"legacy".to_vec();
+    common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        500,
+    )
+    .await
+    .unwrap();
+
+    let (vaa, body, _body_hash) = common::generate_vaa(&emitter, message, nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    common::sync(client, payer).await;
+
+    let account = client.get_account(bridge_key).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), 24);
+
+    // Governance cannot set the quorum until the account has been migrated.
+    let message = GovernancePayloadSetQuorum {
+        numerator: 1,
+        denominator: 2,
+    }
+    .try_to_vec()
+    .unwrap();
+    let (message_key, sequence) =
+        post_governance_vaa(&mut context, client, payer, program, message).await;
+    assert!(common::set_quorum(
+        client,
+        program,
+        payer,
+        message_key,
+        governance.pubkey(),
+        sequence,
+    )
+    .await
+    .is_err());
+
+    // Migrating resizes the account, which needs account data reallocation. solana-program-test
+    // activates every feature, and the resize only works in the BPF runtime, which `cargo
+    // test-bpf` runs the program in.
+    let feature = client
+        .get_account(feature_set::do_support_realloc::id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(feature::from_account(&feature)
+        .unwrap()
+        .activated_at
+        .is_some());
+
+    common::migrate_bridge(client, program, payer)
+        .await
+        .unwrap();
+    common::sync(client, payer).await;
+
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert!(!bridge.legacy_layout);
+    assert_eq!(bridge.config.fee, 500);
+    assert!(bridge.quorum == Quorum::default());
+    assert!(!bridge.paused);
+
+    common::set_quorum(
+        client,
+        program,
+        payer,
+        message_key,
+        governance.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert_eq!(bridge.quorum.numerator, 1);
+    assert_eq!(bridge.quorum.denominator, 2);
+}
+
+#[tokio::test]
+async fn pause_and_unpause() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let governance = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
+
+    // Pause the bridge, allowing a single Solana emitter to keep posting.
+    let allowed = Keypair::new();
+    let message = GovernancePayloadPause {
+        allowlist: vec![AllowedEmitter {
+            chain: 1,
+            address: allowed.pubkey().to_bytes(),
+        }],
+    }
+    .try_to_vec()
+    .unwrap();
+    let (message_key, sequence) =
+        post_governance_vaa(context, client, payer, program, message).await;
+    common::pause(
+        client,
+        program,
+        payer,
+        message_key,
+        governance.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert!(bridge.paused);
+    assert_eq!(
+        bridge.pause_allowlist[0].address,
+        allowed.pubkey().to_bytes()
+    );
+    assert!(bridge.pause_allowlist[1..].iter().all(|e| e.is_empty()));
+
+    // Other emitters can no longer post messages.
+    let emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    assert!(common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        vec![0; 32],
+        10_000
+    )
+    .await
+    .is_err());
+
+    // The allowed emitter can.
+    let nonce = rand::thread_rng().gen();
+    common::post_message(
+        client,
+        program,
+        payer,
+        &allowed,
+        None,
+        nonce,
+        vec![0; 32],
+        10_000,
+    )
+    .await
+    .unwrap();
+
+    // Nor can VAAs from other emitters be posted.
+    let foreign = Keypair::new();
+    let sequence = context.seq.next(foreign.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&foreign, b"paused".to_vec(), nonce, sequence, 0, 2);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    assert!(
+        common::post_vaa(client, program, payer, signature_set, vaa.clone())
+            .await
+            .is_err()
+    );
+
+    // Governance can still post, and unpause the bridge.
+    let message = GovernancePayloadUnpause {}.try_to_vec().unwrap();
+    let (message_key, sequence) =
+        post_governance_vaa(context, client, payer, program, message).await;
+    common::unpause(
+        client,
+        program,
+        payer,
+        message_key,
+        governance.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert!(!bridge.paused);
+    assert!(bridge.pause_allowlist.iter().all(|e| e.is_empty()));
+
+    // Everything flows again.
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    let nonce = rand::thread_rng().gen();
+    common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        vec![0; 32],
+        10_000,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn pause_fails() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let governance = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+
+    // Allowlists are bounded by the space reserved in the Bridge account.
+    let message = GovernancePayloadPause {
+        allowlist: vec![
+            AllowedEmitter {
+                chain: 1,
+                address: [1u8; 32],
+            };
+            bridge::accounts::MAX_PAUSE_ALLOWLIST + 1
+        ],
+    }
+    .try_to_vec()
+    .unwrap();
+    let (message_key, sequence) =
+        post_governance_vaa(context, client, payer, program, message).await;
+    assert!(common::pause(
+        client,
+        program,
+        payer,
+        message_key,
+        governance.pubkey(),
+        sequence,
+    )
+    .await
+    .is_err());
+
+    // Only the governance key can pause the bridge.
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadPause { allowlist: vec![] }
+        .try_to_vec()
+        .unwrap();
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+    let (vaa, body, _body_hash) = common::generate_vaa(&emitter, message, nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    assert!(common::pause(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+    )
+    .await
+    .is_err());
+    common::sync(client, payer).await;
+
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert!(!bridge.paused);
+}
+
+#[tokio::test]
+async fn revoke() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let governance = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let denylist_key = Denylist::<'_, { AccountState::Uninitialized }>::key(None, program);
+
+    // Sign a VAA, whose signature set will be revoked.
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, b"revoked".to_vec(), nonce, sequence, 0, 2);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+
+    // Revoking requires the Denylist to exist.
+    let message = GovernancePayloadRevoke {
+        entry: signature_set.to_bytes(),
+    }
+    .try_to_vec()
+    .unwrap();
+    let (message_key, revoke_sequence) =
+        post_governance_vaa(context, client, payer, program, message).await;
+    assert!(common::revoke(
+        client,
+        program,
+        payer,
+        message_key,
+        governance.pubkey(),
+        revoke_sequence,
+    )
+    .await
+    .is_err());
+
+    // The Denylist is seeded with the legacy entries, and can only be created once.
+    common::initialize_denylist(client, program, payer)
+        .await
+        .unwrap();
+    common::sync(client, payer).await;
+    assert!(common::initialize_denylist(client, program, payer)
+        .await
+        .is_err());
+
+    let denylist: DenylistData = common::get_account_data(client, denylist_key).await;
+    assert_eq!(denylist.entries, DenylistData::legacy().entries);
+    assert_eq!(denylist.entries.len(), 16);
+
+    // The same governance VAA now revokes the signature set.
+    common::revoke(
+        client,
+        program,
+        payer,
+        message_key,
+        governance.pubkey(),
+        revoke_sequence,
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+
+    let denylist: DenylistData = common::get_account_data(client, denylist_key).await;
+    assert!(denylist.contains(&signature_set.to_bytes()));
+    assert!(
+        common::post_vaa(client, program, payer, signature_set, vaa.clone())
+            .await
+            .is_err()
+    );
+
+    // The VAA can still be posted with a new set of signatures.
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+
+    // Revoking a VAA rejects it no matter which signature set is used.
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, b"revoked".to_vec(), nonce, sequence, 0, 2);
+    let message = GovernancePayloadRevoke { entry: body }
+        .try_to_vec()
+        .unwrap();
+    let (message_key, revoke_sequence) =
+        post_governance_vaa(context, client, payer, program, message).await;
+    common::revoke(
+        client,
+        program,
+        payer,
+        message_key,
+        governance.pubkey(),
+        revoke_sequence,
+    )
+    .await
+    .unwrap();
+
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    assert!(
+        common::post_vaa(client, program, payer, signature_set, vaa.clone())
+            .await
+            .is_err()
+    );
+
+    // Unrevoking the VAA accepts it again.
+    let message = GovernancePayloadUnrevoke { entry: body }
+        .try_to_vec()
+        .unwrap();
+    let (message_key, unrevoke_sequence) =
+        post_governance_vaa(context, client, payer, program, message).await;
+    common::unrevoke(
+        client,
+        program,
+        payer,
+        message_key,
+        governance.pubkey(),
+        unrevoke_sequence,
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+
+    let denylist: DenylistData = common::get_account_data(client, denylist_key).await;
+    assert!(!denylist.contains(&body));
+    assert_eq!(denylist.entries.len(), 17);
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+}
+
 #[tokio::test]
 async fn transfer_fees() {
     // Initialize a wormhole bridge on Solana to test with.
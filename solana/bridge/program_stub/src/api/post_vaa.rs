@@ -42,6 +42,12 @@ pub struct PostVAA<'b> {
 
     /// Clock used for timestamping.
     pub clock: Sysvar<'b, Clock>,
+
+    /// Signature sets and VAAs revoked by governance in the bridge, unused by the stub.
+    pub denylist: Info<'b>,
+
+    /// Records the payer of the posted VAA in the bridge, unused by the stub.
+    pub posted_vaa_payer: Info<'b>,
 }
 
 #[derive(Default, BorshSerialize, BorshDeserialize)]